@@ -0,0 +1,254 @@
+use crate::board::{WordError, WordError::*};
+use crate::tile_set::TileSet;
+use AlphabetError::*;
+
+use pyo3::prelude::{pyclass, pymethods};
+
+/// Letter written for a blank standing for the tile `c`: blanks are the uppercase letters
+pub fn to_blank(c: char) -> char {
+	let mut upper = c.to_uppercase();
+	match (upper.next(), upper.next()) {
+		(Some(u), None) => u,
+		_ => c
+	}
+}
+
+/// Tile a blank stands for, tiles being left as they are
+pub fn from_blank(c: char) -> char {
+	let mut lower = c.to_lowercase();
+	match (lower.next(), lower.next()) {
+		(Some(l), None) => l,
+		_ => c
+	}
+}
+
+pub fn is_blank(c: char) -> bool {
+	return c.is_uppercase();
+}
+
+/// Text with blanks and tiles swapped, as the standard notations write tiles in uppercase and blanks in lowercase
+pub fn swap_case(text: &str) -> String {
+	return text.chars().map(|c| match is_blank(c) {
+		true => from_blank(c),
+		false => to_blank(c)
+	}).collect();
+}
+
+/// The word with every blank replaced by the tile it stands for
+pub fn unblank(word: &str) -> String {
+	return word.chars().map(from_blank).collect();
+}
+
+// Letters used for the tiles that are not a single letter whose case can be changed back and forth,
+// the greek ones since a blank is written as the uppercase of its tile
+const SPARE_LETTERS: &str = "αβγδεζηθικλμνξοπρστυφχψω";
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum AlphabetError {
+	InvalidTile(String),
+	TooManyTiles(String)
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+struct AlphabetTile {
+	letter: char,
+	display: String,
+	value: usize
+}
+
+/// Tiles of a language, each one being a letter for the dictionnaries and the board, a text to display, and a value.
+/// A tile displayed with more than one character, like the spanish CH, is written between brackets, as `[ch]`.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[pyclass]
+pub struct Alphabet {
+	tiles: Vec<AlphabetTile>,
+	// Tile indices by decreasing display length, so that texts are read greedily
+	by_length: Vec<usize>
+}
+
+/// The latin letters, with the french values
+impl Default for Alphabet {
+	fn default() -> Self {
+		return TileSet::preset("french").unwrap().alphabet().clone();
+	}
+}
+
+impl Alphabet {
+	/// Alphabet of the tiles, given with their value. A tile that is not a single letter with an uppercase
+	/// form, like `ch`, is written internally with a spare greek letter: an alphabet has at most 24 such tiles,
+	/// minus the greek letters it uses as tiles, and is a TooManyTiles error beyond that.
+	pub fn new(tiles: &[(String, usize)]) -> Result<Alphabet, AlphabetError> {
+		let spare_letters: Vec<char> = SPARE_LETTERS.chars()
+			.filter(|c| !tiles.iter().any(|(display, _)| display.to_lowercase() == c.to_string()))
+			.collect();
+		let nb_spare_letters = spare_letters.len();
+		let mut spare_letters = spare_letters.into_iter();
+		let mut alphabet_tiles: Vec<AlphabetTile> = Vec::with_capacity(tiles.len());
+		for (display, value) in tiles {
+			let display = display.to_lowercase();
+			if display.is_empty() || !display.chars().all(|c| c.is_alphabetic()) {
+				return Err(InvalidTile(format!("alphabet: invalid tile '{}'", display)));
+			}
+			if alphabet_tiles.iter().any(|tile| tile.display == display) {
+				return Err(InvalidTile(format!("alphabet: tile '{}' appears twice", display)));
+			}
+
+			let mut chars = display.chars();
+			let letter = match (chars.next(), chars.next()) {
+				(Some(c), None) if from_blank(to_blank(c)) == c && to_blank(c) != c => c,
+				_ => match spare_letters.next() {
+					Some(c) => c,
+					None => return Err(TooManyTiles(format!(
+						"alphabet: no letter left for tile '{}', at most {} tiles can be other than a single letter with an uppercase",
+						display, nb_spare_letters)))
+				}
+			};
+			alphabet_tiles.push(AlphabetTile{letter, display, value: *value});
+		}
+
+		let mut by_length: Vec<usize> = (0..alphabet_tiles.len()).collect();
+		by_length.sort_by_key(|idx| std::cmp::Reverse(alphabet_tiles[*idx].display.chars().count()));
+		return Ok(Alphabet{tiles: alphabet_tiles, by_length});
+	}
+
+	/// Letters of the tiles, in the order of the alphabet
+	pub fn letters(&self) -> Vec<char> {
+		return self.tiles.iter().map(|tile| tile.letter).collect();
+	}
+
+	/// Sorts words in the order of the alphabet, a blank coming right after its letter
+	pub fn sort_words(&self, words: &mut [String]) {
+		words.sort_by_cached_key(|word| word.chars()
+			.map(|c| (self.tiles.iter().position(|tile| tile.letter == from_blank(c)).unwrap_or(usize::MAX), is_blank(c)))
+			.collect::<Vec<(usize, bool)>>());
+	}
+
+	fn tile(&self, c: char) -> Option<&AlphabetTile> {
+		return self.tiles.iter().find(|tile| tile.letter == c);
+	}
+
+	/// Value of a letter, blanks being worth nothing
+	pub fn value(&self, c: char) -> Result<usize, WordError> {
+		match self.tile(from_blank(c)) {
+			None => Err(UnknownChar("get_value: unknown char".to_string())),
+			Some(_) if is_blank(c) => Ok(0),
+			Some(tile) => Ok(tile.value)
+		}
+	}
+
+	pub fn str_value(&self, word: &str) -> Result<usize, WordError> {
+		return word.chars().map(|c| self.value(c)).sum();
+	}
+
+	/// Text of a letter, uppercase for a blank, and between brackets if longer than one character
+	pub fn display(&self, c: char) -> Option<String> {
+		let tile = self.tile(from_blank(c))?;
+		let text = match is_blank(c) {
+			true => tile.display.to_uppercase(),
+			false => tile.display.clone()
+		};
+		match text.chars().count() {
+			1 => return Some(text),
+			_ => return Some(format!("[{}]", text))
+		};
+	}
+
+	/// Letter of the tile written `text`, without brackets. An uppercase text is a blank.
+	pub fn parse_tile(&self, text: &str) -> Option<char> {
+		let lower = text.to_lowercase();
+		let tile = self.tiles.iter().find(|tile| tile.display == lower)?;
+		if text != lower && text.to_uppercase() == text {
+			return Some(to_blank(tile.letter));
+		}
+		return Some(tile.letter);
+	}
+
+	/// Letters of a text, where tiles are read case insensitively, the longest first, or between brackets.
+	/// The characters for which `keep` is true are left as they are.
+	pub fn encode<F: Fn(char) -> bool>(&self, text: &str, keep: F) -> Result<String, WordError> {
+		return self.encode_tiles(text, keep, false);
+	}
+
+	/// Same as `encode`, but tiles written uppercase are blanks, as in `decode`
+	pub fn encode_with_blanks<F: Fn(char) -> bool>(&self, text: &str, keep: F) -> Result<String, WordError> {
+		return self.encode_tiles(text, keep, true);
+	}
+
+	fn encode_tiles<F: Fn(char) -> bool>(&self, text: &str, keep: F, blanks: bool) -> Result<String, WordError> {
+		let chars: Vec<char> = text.chars().collect();
+		let lower: Vec<char> = chars.iter().map(|c| from_blank(*c)).collect();
+		let mut encoded = String::with_capacity(text.len());
+		let mut i = 0;
+		'text: while i < chars.len() {
+			if keep(chars[i]) {
+				encoded.push(chars[i]);
+				i += 1;
+				continue;
+			}
+			if chars[i] == '[' {
+				let end = match chars[i..].iter().position(|c| *c == ']') {
+					None => return Err(UnknownChar(format!("unclosed bracket in {}", text))),
+					Some(end) => i + end
+				};
+				let inside: String = chars[i+1..end].iter().collect();
+				match self.parse_tile(&inside) {
+					None => return Err(UnknownChar(format!("unknown tile {} in {}", inside, text))),
+					Some(c) if blanks => encoded.push(c),
+					Some(c) => encoded.push(from_blank(c))
+				};
+				i = end + 1;
+				continue;
+			}
+			for idx in &self.by_length {
+				let tile = &self.tiles[*idx];
+				let len = tile.display.chars().count();
+				if i + len <= chars.len() && lower[i..i+len].iter().copied().eq(tile.display.chars()) {
+					match blanks && chars[i..i+len].iter().all(|c| is_blank(*c)) {
+						true => encoded.push(to_blank(tile.letter)),
+						false => encoded.push(tile.letter)
+					};
+					i += len;
+					continue 'text;
+				}
+			}
+			return Err(UnknownChar(format!("unknown letter {} in {}", chars[i], text)));
+		}
+		return Ok(encoded);
+	}
+
+	/// Text of a word made of letters, other characters being left as they are
+	pub fn decode(&self, word: &str) -> String {
+		let mut decoded = String::with_capacity(word.len());
+		for c in word.chars() {
+			match self.display(c) {
+				None => decoded.push(c),
+				Some(text) => decoded.push_str(&text)
+			};
+		}
+		return decoded;
+	}
+}
+
+#[pymethods]
+impl Alphabet {
+	/// Tiles as (text, value) pairs
+	#[new]
+	fn py_new(tiles: Vec<(String, usize)>) -> pyo3::PyResult<Self> {
+		return Ok(Alphabet::new(&tiles)?);
+	}
+
+	/// Latin letters with the french values, used when no alphabet is given
+	#[staticmethod]
+	fn latin() -> Self {
+		return Alphabet::default();
+	}
+
+	pub fn tiles(&self) -> Vec<(String, usize)> {
+		return self.tiles.iter().map(|tile| (tile.display.clone(), tile.value)).collect();
+	}
+}
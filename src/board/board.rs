@@ -1,70 +1,66 @@
 use crate::board::BoardService;
 use crate::board::{DeserializingError, DeserializingError::*};
 use crate::board::{WordError, WordError::*};
+use crate::board::MoveError;
+use crate::board::BoardProblem;
 use crate::board::WordToFill;
 use crate::board::PotentialWordConditionsBuilder;
-use crate::board::{SIDE, SIZE};
+use crate::board::LineSquare;
+use crate::board::Layout;
+use crate::board::ScoreBreakdown;
 
-use crate::board::values::*;
 use crate::board::tile::*;
+use crate::alphabet::{Alphabet, to_blank, from_blank, is_blank, unblank};
+use std::sync::Arc;
 use Tile::*;
 use PlayedTile::*;
 use BoardTile::*;
 
 use crate::board::transposition::*;
+use crate::solver::BestWord;
+use crate::str_tree::WordLookup;
+use crate::tile_set::TileSet;
 
+use pyo3::prelude::{pyclass, pymethods};
+
+/// Played tiles and premium squares are stored apart, so that a square keeps its premium under a tile
 #[derive(Debug)]
+#[derive(PartialEq)]
+#[pyclass]
 pub struct Board {
-	tiles: [Tile; SIZE]
+	tiles: Vec<Option<PlayedTile>>,
+	premiums: Vec<BoardTile>,
+	layout: Arc<Layout>,
+	alphabet: Arc<Alphabet>
 }
 
 impl BoardService for Board {
 	fn serialize<T: TransposedState>(&self) -> String {
-		let mut message = "".to_string();
-		for x in 0..SIDE {
-			for y in 0..SIDE {
-				message.push( match self.at::<T>(x, y) {
-					Board(EmptyTile) => '_',
-					Played(LetterTile(c)) => c,
-					Played(JokerTile(c)) => c.to_ascii_uppercase(),
-					Board(WordBonusTile(n)) => (n+3).to_string().chars().nth(0).unwrap(),
-					Board(LetterBonusTile(n)) => n.to_string().chars().nth(0).unwrap()
-				});
-				message.push(' ');
-			}
-			message.push('\n');
-		}
-		return message;
-	}
-
-	fn deserialize(message: &str) -> Result<Board, DeserializingError> {
-		let mut board = Board::new_empty();
-
-		let mut tile_nb:usize = 0;
-		for char in message.chars() {
-			board.tiles[tile_nb] = match char {
-				'_' => Board(EmptyTile),
-				'2' => Board(LetterBonusTile(2)),
-				'3' => Board(LetterBonusTile(3)),
-				'5' => Board(WordBonusTile(2)),
-				'6' => Board(WordBonusTile(3)),
-				c => {
-					if c.is_ascii_lowercase() {
-						Played(LetterTile(c))
-					} else if c.is_ascii_uppercase() {
-						Played(JokerTile(c.to_ascii_lowercase()) )
-					} else { 
-						return Err(UnknownSymbol("deserialize: unknown symbol".to_string())); 
-					}
-				}
-			};
-			tile_nb += 1;
+		let side = self.layout.side();
+		return (0..side*side).map(|idx| self.symbol::<T>(idx / side, idx % side)).collect();
+	}
+
+	fn pretty<T: TransposedState>(&self) -> String {
+		let side = self.layout.side();
+		let mut text = "  ".to_string();
+		for y in 0..side {
+			text.push(' ');
+			text.push((b'A' + y as u8) as char);
 		}
-		if tile_nb != SIZE {
-			return Err(WrongLength("deserialize: wrong length".to_string()));
+		text.push('\n');
+		for x in 0..side {
+			text.push_str(&format!("{:>2}", x + 1));
+			for y in 0..side {
+				text.push(' ');
+				text.push_str(&self.symbol::<T>(x, y));
+			}
+			text.push('\n');
 		}
+		return text;
+	}
 
-		return Ok(board);
+	fn deserialize<T: TransposedState>(message: &str) -> Result<Board, DeserializingError> {
+		return Board::read::<T>(message, &Arc::new(Alphabet::default()), &Arc::new(Layout::default()));
 	}
 
 	fn get_conditions<T: TransposedState, PWCB>(&self, x: usize, y: usize, conditions: &mut PWCB)
@@ -76,7 +72,7 @@ impl BoardService for Board {
 		let mut nb_letters = 0;
 		let mut at_least_one_constraints = false;
 
-		for relative_y in 0u8..((SIDE-y) as u8) {
+		for relative_y in 0u8..((self.layout.side()-y) as u8) {
 			let absolute_y = y + relative_y as usize;
 
 			// Case: tile is occupied: register letter and continue
@@ -86,16 +82,16 @@ impl BoardService for Board {
 					conditions.add_nb_letters(nb_letters);
 				}
 				at_least_one_constraints = true;
-				conditions.add_letter(c.to_ascii_lowercase(), relative_y);
+				conditions.add_letter(from_blank(c), relative_y);
 				continue;
 			}
 
-			if nb_letters == 7 { return; }
+			if nb_letters as usize == self.layout.rack_size() { return; }
 
 			// Find letters above and/or below: a word to fill
 			match WordToFill::new(
-				self.get_above::<T>(x, absolute_y).to_ascii_lowercase(), 
-				self.get_below::<T>(x, absolute_y).to_ascii_lowercase()) {
+				unblank(&self.get_above::<T>(x, absolute_y)), 
+				unblank(&self.get_below::<T>(x, absolute_y))) {
 					Err(_) => (),
 					Ok(word) => {
 						at_least_one_constraints = true;
@@ -112,80 +108,514 @@ impl BoardService for Board {
 	}
 
 	fn get_score<T: TransposedState>(&self, word: &[char], x: usize, y: usize) -> Result<usize, WordError> {
-		let mut word_bonus: usize = 1;
-		let mut word_value: usize = 0;
-		let mut other_words_formed: usize = 0;
+		return Ok(self.get_score_breakdown::<T>(word, x, y)?.total());
+	}
+
+	fn get_score_breakdown<T: TransposedState>(&self, word: &[char], x: usize, y: usize) -> Result<ScoreBreakdown, WordError> {
+		let mut breakdown = ScoreBreakdown{word_multiplier: 1, ..Default::default()};
+		let mut main_value: usize = 0;
 
 		let mut nb_letters = 0;
 
-		for (c, relative_y) in word.iter().zip(0..word.len()) {
+		for (relative_y, c) in word.iter().enumerate() {
 			let absolute_y = y + relative_y;
 			let mut local_letter_bonus = 1;
 			let mut local_word_bonus = 1;
 
-			word_value += match (c, self.at::<T>(x, absolute_y)) {
-				// Case of constraint: there must be a letter on the board
-				('_', Played(JokerTile(_))) => {0; continue},
-				('_', Played(LetterTile(c2))) => {get_value_lowercase(c2); continue},
+			let value = match (c, self.at::<T>(x, absolute_y)) {
+				// Case of constraint: there must be a letter on the board, which counts without its premium
+				('_', Played(JokerTile(c2))) => {
+					breakdown.main_word.push(to_blank(c2));
+					breakdown.letter_multipliers.push(1);
+					continue
+				},
+				('_', Played(LetterTile(c2))) => {
+					let value = self.alphabet.value(c2)?;
+					breakdown.main_word.push(c2);
+					breakdown.letter_multipliers.push(1);
+					breakdown.base_value += value;
+					main_value += value;
+					continue
+				},
 				('_', _) => return Err(UnexpectedUnderscore("get_score: unexpected void".to_string())),
 
 				// Case of letter: there must be no letter on the board
-				(_, Board(EmptyTile)) => {
-					nb_letters += 1;
-					get_value(*c)?
-				},
+				(_, Board(EmptyTile)) => self.alphabet.value(*c)?,
 				(_, Board(LetterBonusTile(n))) => {
-					nb_letters += 1;
 					local_letter_bonus = n as usize;
-					local_letter_bonus * get_value(*c)?
+					self.alphabet.value(*c)?
 				},
 				(_, Board(WordBonusTile(n))) => {
-					nb_letters += 1;
 					local_word_bonus = n as usize;
-					word_bonus *= local_word_bonus;
-					get_value(*c)?
+					self.alphabet.value(*c)?
 				}
 
 				(_,_) => return Err(TileOccupied("get_score: Tile occupied".to_string()))
 			};
+			nb_letters += 1;
+			breakdown.main_word.push(*c);
+			breakdown.letter_multipliers.push(local_letter_bonus);
+			breakdown.word_multiplier *= local_word_bonus;
+			breakdown.base_value += value;
+			main_value += local_letter_bonus * value;
 
 			// Find letters above and/or below: a word filled
 			match WordToFill::new(self.get_above::<T>(x, absolute_y), self.get_below::<T>(x, absolute_y)) {
 				Err(_) => (),
 				Ok(word) => {
-					other_words_formed += 
-						local_word_bonus * get_str_value(&word.complete(*c))? + 
-						(local_letter_bonus-1) * get_value(*c)?;
+					let cross_word = word.complete(*c);
+					let score = 
+						local_word_bonus * self.alphabet.str_value(&cross_word)? + 
+						(local_letter_bonus-1) * value;
+					breakdown.cross_words.push((cross_word, score));
 				}
 			};
 		}
 
-		if nb_letters == 7 {
-			other_words_formed += 50;
+		breakdown.main_score = main_value * breakdown.word_multiplier;
+		if nb_letters == self.layout.rack_size() {
+			breakdown.bingo_bonus = self.layout.bingo_bonus();
+		}
+
+		return Ok(breakdown);
+	}
+
+	fn get_line<T: TransposedState>(&self, x: usize, line: &mut Vec<LineSquare>) {
+		line.clear();
+		let empty_board = self.is_empty();
+
+		for y in 0..self.layout.side() {
+			line.push(match self.at::<T>(x, y).letter() {
+				Some(c) => LineSquare::Letter(from_blank(c)),
+				None => LineSquare::Empty{
+					anchor: self.has_neighbour::<T>(x, y) || (empty_board && T::transposed_coord(x, y) == self.layout.start()),
+					cross_word: WordToFill::new(
+						unblank(&self.get_above::<T>(x, y)), 
+						unblank(&self.get_below::<T>(x, y))).ok()
+				}
+			});
 		}
+	}
+
+	fn is_empty(&self) -> bool {
+		return !self.tiles.iter().any(|tile| tile.is_some());
+	}
 
-		return Ok(word_value * word_bonus + other_words_formed);
+	fn layout(&self) -> &Layout {
+		return &self.layout;
 	}
 }
 
 impl Board {
-	fn new_empty() -> Board {
-		return Board{tiles: [Board(EmptyTile); SIZE]};
+	/// Board with no tile, before the first move
+	pub fn new_empty(alphabet: &Arc<Alphabet>, layout: &Arc<Layout>) -> Board {
+		let side = layout.side();
+		return Board{
+			tiles: vec![None; side*side],
+			premiums: (0..side*side).map(|idx| layout.premium(idx / side, idx % side)).collect(),
+			layout: layout.clone(),
+			alphabet: alphabet.clone()};
+	}
+
+	/// Same as `deserialize`, with the letters of the alphabet and the squares of the layout.
+	/// A tile of more than one character is written between brackets, and `_` is the premium square of the layout,
+	/// which also stays under the letters.
+	pub fn deserialize_with(message: &str, alphabet: &Arc<Alphabet>, layout: &Arc<Layout>) -> Result<Board, DeserializingError> {
+		return Board::read::<NotTransposed>(message, alphabet, layout);
+	}
+
+	// Board of a message in the compact format, or in a grid, the squares being in the order of `T`
+	fn read<T: TransposedState>(message: &str, alphabet: &Arc<Alphabet>, layout: &Arc<Layout>) -> Result<Board, DeserializingError> {
+		let mut board = Board::new_empty(alphabet, layout);
+		let side = layout.side();
+		let size = board.tiles.len();
+		let mut problems = Vec::new();
+		let message = match message.contains(char::is_whitespace) {
+			true => Board::grid_squares(message, side, &mut problems),
+			false => message.to_string()
+		};
+
+		// Premium and tile of each square, in the order of the message
+		let mut squares: Vec<(Option<BoardTile>, Option<PlayedTile>)> = Vec::with_capacity(size);
+		let mut chars = message.chars();
+		while let Some(char) = chars.next() {
+			let (row, column) = (squares.len() / side + 1, squares.len() % side + 1);
+			match (char, BoardTile::from_symbol(char)) {
+				('_', _) => squares.push((None, None)),
+				(_, Some(premium)) => squares.push((Some(premium), None)),
+				(c, None) => {
+					// A tile between brackets that are never closed takes the end of the message
+					let (tile, symbol) = match (c, chars.as_str().split_once(']')) {
+						('[', Some((tile, rest))) => {
+							chars = rest.chars();
+							(alphabet.parse_tile(tile), format!("[{}]", tile))
+						},
+						('[', None) => (None, format!("[{}", chars.by_ref().collect::<String>())),
+						(c, _) => (alphabet.parse_tile(&c.to_string()), c.to_string())
+					};
+					match tile {
+						None => {
+							problems.push(UnknownSymbol{symbol, row, column});
+							squares.push((None, None));
+						},
+						Some(c) if is_blank(c) => squares.push((None, Some(JokerTile(from_blank(c))))),
+						Some(c) => squares.push((None, Some(LetterTile(c))))
+					};
+				}
+			};
+		}
+		if squares.len() != size {
+			problems.push(WrongLength{expected: size, found: squares.len()});
+		}
+		match problems.len() {
+			0 => (),
+			1 => return Err(problems.pop().unwrap()),
+			_ => return Err(Several(problems))
+		};
+
+		for (square_nb, (premium, tile)) in squares.into_iter().enumerate() {
+			let (x, y) = T::transposed_coord(square_nb / side, square_nb % side);
+			if let Some(premium) = premium {
+				board.premiums[x*side + y] = premium;
+			}
+			board.tiles[x*side + y] = tile;
+		}
+		return Ok(board);
+	}
+
+	// Squares of a grid, one line per row with its symbols separated by spaces.
+	// A row may start with its number, and a line with the letters of the columns may come first.
+	fn grid_squares(message: &str, side: usize, problems: &mut Vec<DeserializingError>) -> String {
+		let column_labels: Vec<String> = (0..side).map(|y| ((b'A' + y as u8) as char).to_string()).collect();
+		let mut squares = String::new();
+		let mut rows = 0;
+		for line in message.lines() {
+			let symbols: Vec<&str> = line.split_whitespace().collect();
+			if symbols.is_empty() || (rows == 0 && symbols == column_labels) {
+				continue;
+			}
+			rows += 1;
+			match symbols.len() {
+				n if n == side + 1 && symbols[0].parse::<usize>().is_ok() => squares.extend(symbols[1..].iter().copied()),
+				_ => squares.extend(symbols)
+			};
+		}
+		if rows != side {
+			problems.push(WrongRowCount{expected: side, found: rows});
+		}
+		return squares;
+	}
+
+	/// Places the tiles of a move and returns its score. The letters of the word must fall on empty squares,
+	/// and its `_` on the tiles it plays through. The board is left unchanged on error.
+	pub fn apply_move(&mut self, mv: &BestWord) -> Result<usize, WordError> {
+		let squares = self.move_squares(mv)?;
+		let word: Vec<char> = mv.word.chars().collect();
+		if word.iter().all(|c| *c == '_') {
+			return Err(UnexpectedUnderscore("apply_move: no tile placed".to_string()));
+		}
+		let score = match mv.vertical {
+			false => self.get_score::<NotTransposed>(&word, mv.coord.0, mv.coord.1)?,
+			true => self.get_score::<Transposed>(&word, mv.coord.1, mv.coord.0)?
+		};
+
+		for (c, idx) in word.iter().zip(squares) {
+			self.tiles[idx] = match *c {
+				'_' => continue,
+				c if is_blank(c) => Some(JokerTile(from_blank(c))),
+				c => Some(LetterTile(c))
+			};
+		}
+		return Ok(score);
+	}
+
+	/// Takes back the tiles placed by `apply_move`, which must all still be on the board
+	pub fn undo_move(&mut self, mv: &BestWord) -> Result<(), WordError> {
+		let squares = self.move_squares(mv)?;
+		let placed: Vec<(char, usize)> = mv.word.chars().zip(squares).filter(|(c, _)| *c != '_').collect();
+		for (c, idx) in &placed {
+			match self.tiles[*idx] {
+				Some(tile) if Played(tile).letter() == Some(*c) => (),
+				_ => return Err(MissingTile(format!("undo_move: {} is not on the board", c)))
+			};
+		}
+		for (_, idx) in placed {
+			self.tiles[idx] = None;
+		}
+		return Ok(());
+	}
+
+	/// Checks a proposed move against the rules and returns how it scores.
+	/// The word covers the squares from `coord` rightwards, or downwards if `vertical`, and is written with
+	/// the letters of the alphabet, blanks in uppercase. Its letters on occupied squares must be the ones
+	/// of the board, or `_`. `rack` is the letter set of the player, `0` being a blank.
+	pub fn validate_move<D: WordLookup>(&self, word: &str, coord: (usize, usize), vertical: bool, rack: &str, dict: &D)
+	-> Result<ScoreBreakdown, MoveError> {
+		let word: Vec<char> = word.chars().collect();
+		match vertical {
+			false => return self.check_move::<NotTransposed, D>(&word, coord.0, coord.1, rack, dict),
+			true => return self.check_move::<Transposed, D>(&word, coord.1, coord.0, rack, dict)
+		};
+	}
+
+	fn check_move<T: TransposedState, D: WordLookup>(&self, word: &[char], x: usize, y: usize, rack: &str, dict: &D)
+	-> Result<ScoreBreakdown, MoveError> {
+		let side = self.layout.side();
+		if word.is_empty() {
+			return Err(MoveError::NoTilePlaced("validate_move: empty word".to_string()));
+		}
+		if x >= side || y + word.len() > side {
+			return Err(MoveError::OutOfBoard("validate_move: the word goes out of the board".to_string()));
+		}
+
+		// Word as scored, with `_` on the tiles played through, and as read on the board
+		let mut played: Vec<char> = Vec::with_capacity(word.len());
+		let mut spelled = String::new();
+		for (relative_y, c) in word.iter().enumerate() {
+			let (row, col) = T::transposed_coord(x, y + relative_y);
+			match (*c, self.at::<T>(x, y + relative_y).letter()) {
+				('_', None) => return Err(MoveError::NotContiguous(
+					format!("validate_move: the square ({}, {}) is empty", row, col))),
+				(c, None) => {
+					played.push(c);
+					spelled.push(c);
+				},
+				(c, Some(letter)) if c == '_' || from_blank(c) == from_blank(letter) => {
+					played.push('_');
+					spelled.push(letter);
+				},
+				(_, Some(_)) => return Err(MoveError::TileOccupied(
+					format!("validate_move: the square ({}, {}) is occupied", row, col)))
+			};
+		}
+		if played.iter().all(|c| *c == '_') {
+			return Err(MoveError::NoTilePlaced("validate_move: no tile placed".to_string()));
+		}
+		let before = y > 0 && self.at::<T>(x, y - 1).is_occupied();
+		let after = y + word.len() < side && self.at::<T>(x, y + word.len()).is_occupied();
+		if before || after {
+			return Err(MoveError::NotContiguous("validate_move: the word goes on beyond its ends".to_string()));
+		}
+
+		let new_tiles: Vec<(usize, char)> = played.iter().enumerate()
+			.filter(|(_, c)| **c != '_')
+			.map(|(relative_y, c)| (y + relative_y, *c))
+			.collect();
+		if self.is_empty() {
+			if !new_tiles.iter().any(|(yy, _)| T::transposed_coord(x, *yy) == self.layout.start()) {
+				return Err(MoveError::StartNotCovered("validate_move: the first move must cover the start square".to_string()));
+			}
+		} else if new_tiles.len() == word.len() && !new_tiles.iter().any(|(yy, _)| self.has_neighbour::<T>(x, *yy)) {
+			return Err(MoveError::NotConnected("validate_move: the word touches no tile of the board".to_string()));
+		}
+
+		let mut rack: Vec<char> = rack.chars().collect();
+		for (_, c) in &new_tiles {
+			let tile = match is_blank(*c) {
+				true => '0',
+				false => *c
+			};
+			match rack.iter().position(|r| *r == tile) {
+				None => return Err(MoveError::NotInRack(
+					format!("validate_move: {} is not in the rack", self.alphabet.decode(&tile.to_string())))),
+				Some(idx) => rack.remove(idx)
+			};
+		}
+
+		let mut cross_words = 0;
+		for (yy, c) in &new_tiles {
+			if let Ok(cross_word) = WordToFill::new(self.get_above::<T>(x, *yy), self.get_below::<T>(x, *yy)) {
+				let cross_word = unblank(&cross_word.complete(*c));
+				if !dict.is_word(&cross_word) {
+					return Err(MoveError::NotAWord(format!("validate_move: {} is not a word", self.alphabet.decode(&cross_word))));
+				}
+				cross_words += 1;
+			}
+		}
+		let spelled = unblank(&spelled);
+		if (spelled.chars().count() > 1 || cross_words == 0) && !dict.is_word(&spelled) {
+			return Err(MoveError::NotAWord(format!("validate_move: {} is not a word", self.alphabet.decode(&spelled))));
+		}
+
+		return self.get_score_breakdown::<T>(&played, x, y).map_err(MoveError::Scoring);
+	}
+
+	// Same move, with the word written with the letters of the alphabet
+	fn encode_move(&self, mv: &BestWord) -> Result<BestWord, WordError> {
+		let mut encoded = mv.clone();
+		encoded.word = self.alphabet.encode_with_blanks(&mv.word, |c| c == '_')?;
+		return Ok(encoded);
+	}
+
+	// Indices of the squares covered by a move
+	fn move_squares(&self, mv: &BestWord) -> Result<Vec<usize>, WordError> {
+		let side = self.layout.side();
+		let (x, y) = mv.coord;
+		let len = mv.word.chars().count();
+		let (last_x, last_y) = match mv.vertical {
+			false => (x, y + len.max(1) - 1),
+			true => (x + len.max(1) - 1, y)
+		};
+		if last_x >= side || last_y >= side {
+			return Err(OutOfBoard("move: the word goes out of the board".to_string()));
+		}
+		return Ok((0..len).map(|i| match mv.vertical {
+			false => x*side + y + i,
+			true => (x + i)*side + y
+		}).collect());
+	}
+
+	/// Every problem of the position: the start square left empty while tiles are on the board, the words
+	/// that are not in the dictionary, the groups of tiles not connected to the others and,
+	/// given a tile set, the letters with more tiles on the board than in the set
+	pub fn check<D: WordLookup>(&self, dict: &D, tile_set: Option<&TileSet>) -> Vec<BoardProblem> {
+		let mut problems = Vec::new();
+		if self.is_empty() {
+			return problems;
+		}
+		let side = self.layout.side();
+		let start = self.layout.start();
+		if self.tiles[start.0*side + start.1].is_none() {
+			problems.push(BoardProblem::StartEmpty);
+		}
+		self.check_words::<NotTransposed, D>(dict, false, &mut problems);
+		self.check_words::<Transposed, D>(dict, true, &mut problems);
+
+		let mut groups = self.groups();
+		let main = match groups.iter().position(|group| group.contains(&start)) {
+			Some(main) => main,
+			None => groups.iter().enumerate().max_by_key(|(nb, group)| (group.len(), std::cmp::Reverse(*nb))).unwrap().0
+		};
+		groups.remove(main);
+		problems.extend(groups.into_iter().map(BoardProblem::Disconnected));
+
+		if let Some(tile_set) = tile_set {
+			let mut counts: Vec<(char, usize)> = Vec::new();
+			for tile in self.tiles.iter().flatten() {
+				let letter = match tile {
+					LetterTile(c) => *c,
+					JokerTile(_) => '0'
+				};
+				match counts.iter_mut().find(|(c, _)| *c == letter) {
+					Some((_, count)) => *count += 1,
+					None => counts.push((letter, 1))
+				};
+			}
+			counts.sort();
+			for (letter, count) in counts {
+				let (tile, available) = match letter {
+					'0' => ("0".to_string(), tile_set.blanks()),
+					c => (self.alphabet.decode(&c.to_string()), tile_set.count(c))
+				};
+				if count > available {
+					problems.push(BoardProblem::TooManyTiles{tile, count, available});
+				}
+			}
+		}
+		return problems;
+	}
+
+	// Words of two letters or more along the lines of `T` which aren't in the dictionary,
+	// and also the tiles without neighbour when `vertical` is false
+	fn check_words<T: TransposedState, D: WordLookup>(&self, dict: &D, vertical: bool, problems: &mut Vec<BoardProblem>) {
+		let side = self.layout.side();
+		for x in 0..side {
+			let mut y = 0;
+			while y < side {
+				let first = y;
+				let mut word = String::new();
+				while let Some(c) = self.at_nopanic::<T>(x, y).and_then(|tile| tile.letter()) {
+					word.push(c);
+					y += 1;
+				}
+				let length = y - first;
+				y += 1;
+				let lone = length == 1 && !vertical && !self.has_neighbour::<T>(x, first);
+				if (length > 1 || lone) && !dict.is_word(&unblank(&word)) {
+					problems.push(BoardProblem::NotAWord{
+						word: self.alphabet.decode(&word),
+						coord: T::transposed_coord(x, first),
+						vertical});
+				}
+			}
+		}
+	}
+
+	// Squares of each group of tiles touching one another, in the order of their first square
+	fn groups(&self) -> Vec<Vec<(usize, usize)>> {
+		let side = self.layout.side();
+		let mut seen = vec![false; side*side];
+		let mut groups = Vec::new();
+		for idx in 0..side*side {
+			if seen[idx] || self.tiles[idx].is_none() {
+				continue;
+			}
+			seen[idx] = true;
+			let mut group = Vec::new();
+			let mut to_visit = vec![(idx / side, idx % side)];
+			while let Some((x, y)) = to_visit.pop() {
+				group.push((x, y));
+				for (xx, yy) in [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)] {
+					if xx < side && yy < side && !seen[xx*side + yy] && self.tiles[xx*side + yy].is_some() {
+						seen[xx*side + yy] = true;
+						to_visit.push((xx, yy));
+					}
+				}
+			}
+			group.sort();
+			groups.push(group);
+		}
+		return groups;
 	}
 
 	// Accessors
 	fn at<T: TransposedState>(&self, x: usize, y:usize) -> Tile {
 		let (x_transposed, y_transposed) = T::transposed_coord(x, y);
-		return self.tiles[x_transposed*SIDE + y_transposed];
+		let idx = x_transposed*self.layout.side() + y_transposed;
+		match self.tiles[idx] {
+			Some(played) => return Played(played),
+			None => return Board(self.premiums[idx])
+		};
 	}
-	#[allow(dead_code)]
+
+	/// Premium squares as a board message, whether tiles are on them or not
+	pub fn premiums(&self) -> String {
+		return self.premiums.iter().map(|premium| premium.symbol()).collect();
+	}
+
+	/// Board message of the tiles, the empty squares being written with their premium
+	pub fn message(&self) -> String {
+		return self.serialize::<NotTransposed>();
+	}
+
+	// Symbol of a square in the board messages: its tile, or else its premium
+	fn symbol<T: TransposedState>(&self, x: usize, y: usize) -> String {
+		match self.at::<T>(x, y) {
+			Played(LetterTile(c)) => return self.alphabet.display(c).unwrap(),
+			Played(JokerTile(c)) => return self.alphabet.display(to_blank(c)).unwrap(),
+			Board(premium) => return premium.symbol().to_string()
+		};
+	}
+
 	fn at_nopanic<T: TransposedState>(&self, x: usize, y: usize) -> Option<Tile> {
-		if x >= SIDE || y >= SIDE {
+		if x >= self.layout.side() || y >= self.layout.side() {
 			return None;
 		}
 		return Some(self.at::<T>(x, y));
 	}
 
+	fn has_neighbour<T: TransposedState>(&self, x: usize, y: usize) -> bool {
+		let neighbours = [
+			(x.wrapping_sub(1), y), 
+			(x+1, y), 
+			(x, y.wrapping_sub(1)), 
+			(x, y+1)];
+		return neighbours.iter().any(|&(xx, yy)| match self.at_nopanic::<T>(xx, yy) {
+			None => false,
+			Some(tile) => tile.is_occupied()
+		});
+	}
+
 	fn get_above<T: TransposedState>(&self, x: usize, y: usize) -> String {
 		let mut above = "".to_string();
 		for xx in 1u8..((x+1) as u8) {
@@ -200,7 +630,7 @@ impl Board {
 
 	fn get_below<T: TransposedState>(&self, x: usize, y: usize) -> String {
 		let mut below = "".to_string();
-		for xx in 1u8..((SIDE-x) as u8) {
+		for xx in 1u8..((self.layout.side()-x) as u8) {
 			match self.at::<T>(x+xx as usize, y).letter() {
 				Some(c) => below.push(c),
 				None => break
@@ -209,3 +639,49 @@ impl Board {
 		return below;
 	}
 }
+
+#[pymethods]
+impl Board {
+	/// Board of a message written with the tiles of `alphabet`, on `layout`
+	#[new]
+	#[args(alphabet = "None", layout = "None")]
+	fn py_new(message: &str, alphabet: Option<Alphabet>, layout: Option<Layout>) -> pyo3::PyResult<Self> {
+		return Ok(Board::deserialize_with(
+			message,
+			&Arc::new(alphabet.unwrap_or_default()),
+			&Arc::new(layout.unwrap_or_default()))?);
+	}
+
+	/// Places the tiles of a move, as returned by a `WordFinder`, and returns its score
+	#[pyo3(name = "apply_move")]
+	fn py_apply_move(&mut self, mv: &BestWord) -> pyo3::PyResult<usize> {
+		let mv = self.encode_move(mv)?;
+		return Ok(self.apply_move(&mv)?);
+	}
+
+	#[pyo3(name = "undo_move")]
+	fn py_undo_move(&mut self, mv: &BestWord) -> pyo3::PyResult<()> {
+		let mv = self.encode_move(mv)?;
+		return Ok(self.undo_move(&mv)?);
+	}
+
+	#[pyo3(name = "message")]
+	fn py_message(&self) -> String {
+		return self.message();
+	}
+
+	#[pyo3(name = "premiums")]
+	fn py_premiums(&self) -> String {
+		return self.premiums();
+	}
+
+	#[pyo3(name = "is_empty")]
+	fn py_is_empty(&self) -> bool {
+		return self.is_empty();
+	}
+
+	/// Grid with labelled rows and columns, which the constructor reads back
+	fn __str__(&self) -> String {
+		return self.pretty::<NotTransposed>();
+	}
+}
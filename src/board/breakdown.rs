@@ -0,0 +1,57 @@
+use pyo3::prelude::{pyclass, pymethods};
+
+/// How the score of a move is made. The letters of the main word are multiplied by the letter premiums
+/// under the new tiles, then the whole word by the word premiums. The cross words formed and the bingo bonus
+/// are added on top.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq)]
+#[pyclass]
+pub struct ScoreBreakdown {
+	/// Main word as read on the board once the move is played
+	#[pyo3(get)]
+	pub main_word: String,
+	/// Sum of the values of the letters of the main word, without premiums
+	#[pyo3(get)]
+	pub base_value: usize,
+	/// Letter premium of each letter of the main word, 1 for the tiles played through
+	#[pyo3(get)]
+	pub letter_multipliers: Vec<usize>,
+	#[pyo3(get)]
+	pub word_multiplier: usize,
+	/// Score of the main word, premiums included
+	#[pyo3(get)]
+	pub main_score: usize,
+	/// Every word formed across the main word, with its score
+	#[pyo3(get)]
+	pub cross_words: Vec<(String, usize)>,
+	#[pyo3(get)]
+	pub bingo_bonus: usize
+}
+
+impl ScoreBreakdown {
+	pub fn total(&self) -> usize {
+		return self.main_score + self.cross_words.iter().map(|(_, score)| score).sum::<usize>() + self.bingo_bonus;
+	}
+}
+
+#[pymethods]
+impl ScoreBreakdown {
+	#[pyo3(name = "total")]
+	fn py_total(&self) -> usize {
+		return self.total();
+	}
+
+	fn __str__(&self) -> String {
+		let mut ret = format!("{}: {}", self.main_word, self.main_score);
+		for (word, score) in &self.cross_words {
+			ret.push_str(&format!(" + {}: {}", word, score));
+		}
+		if self.bingo_bonus > 0 {
+			ret.push_str(&format!(" + bingo: {}", self.bingo_bonus));
+		}
+		ret.push_str(&format!(" = {}", self.total()));
+		return ret;
+	}
+}
@@ -0,0 +1,279 @@
+use crate::board::{LayoutError, LayoutError::*};
+use crate::board::MAX_SIDE;
+use crate::board::tile::BoardTile;
+
+use pyo3::prelude::{pyclass, pymethods};
+use pyo3::exceptions::PyValueError;
+
+const SCRABBLE: [&str; 15] = [
+	"6__2___6___2__6",
+	"_5___3___3___5_",
+	"__5___2_2___5__",
+	"2__5___2___5__2",
+	"____5_____5____",
+	"_3___3___3___3_",
+	"__2___2_2___2__",
+	"6__2___5___2__6",
+	"__2___2_2___2__",
+	"_3___3___3___3_",
+	"____5_____5____",
+	"2__5___2___5__2",
+	"__5___2_2___5__",
+	"_5___3___3___5_",
+	"6__2___6___2__6"];
+
+const WORDS_WITH_FRIENDS: [&str; 15] = [
+	"___6__3_3__6___",
+	"__2__5___5__2__",
+	"_2__2_____2__2_",
+	"6__3___5___3__6",
+	"__2___2_2___2__",
+	"_5___3___3___5_",
+	"3___2_____2___3",
+	"___5_______5___",
+	"3___2_____2___3",
+	"_5___3___3___5_",
+	"__2___2_2___2__",
+	"6__3___5___3__6",
+	"_2__2_____2__2_",
+	"__2__5___5__2__",
+	"___6__3_3__6___"];
+
+const SUPER_SCRABBLE: [&str; 21] = [
+	"7__2___6__2__6___2__7",
+	"_5__3___5___5___3__5_",
+	"__5__4___5_5___4__5__",
+	"2__6__2___6___2__6__2",
+	"_3__5___3___3___5__3_",
+	"__4__5___2_2___5__4__",
+	"___2__5___2___5__2___",
+	"6______5_____5______6",
+	"_5__3___3___3___3__5_",
+	"__5__2___2_2___2__5__",
+	"2__6__2___5___2__6__2",
+	"__5__2___2_2___2__5__",
+	"_5__3___3___3___3__5_",
+	"6______5_____5______6",
+	"___2__5___2___5__2___",
+	"__4__5___2_2___5__4__",
+	"_3__5___3___3___5__3_",
+	"2__6__2___6___2__6__2",
+	"__5__4___5_5___4__5__",
+	"_5__3___5___5___3__5_",
+	"7__2___6__2__6___2__7"];
+
+const RACK_SIZE: usize = 7;
+const BINGO_BONUS: usize = 50;
+
+/// Name, premium squares and bingo bonus of the built-in layouts, which start at the center
+const PRESETS: [(&str, &[&str], usize); 3] = [
+	("scrabble", &SCRABBLE, BINGO_BONUS),
+	("words_with_friends", &WORDS_WITH_FRIENDS, 35),
+	("super_scrabble", &SUPER_SCRABBLE, BINGO_BONUS)];
+
+/// Square board: its side, the premium square under each square, and the square the first move has to cover,
+/// along with the number of tiles of a rack and the bonus for playing all of them.
+///
+/// As text, a layout is a grid of board message symbols, one line per row, and metadata lines
+/// `start <row> <column>`, `rack <size>` and `bingo <bonus>`. Blank lines and lines starting with `#` are ignored.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[pyclass]
+pub struct Layout {
+	side: usize,
+	premiums: Vec<BoardTile>,
+	start: (usize, usize),
+	rack_size: usize,
+	bingo_bonus: usize
+}
+
+/// 15×15 board without premium squares, the premiums being then given by the board messages
+impl Default for Layout {
+	fn default() -> Self {
+		return Layout::plain(15).unwrap();
+	}
+}
+
+impl Layout {
+	/// `premiums` has one board message symbol per square, row by row, `_` being a plain square
+	pub fn new(side: usize, premiums: &str, start: (usize, usize)) -> Result<Layout, LayoutError> {
+		if side == 0 || side > MAX_SIDE {
+			return Err(WrongSize(format!("layout: the side must be between 1 and {}", MAX_SIDE)));
+		}
+		if start.0 >= side || start.1 >= side {
+			return Err(StartOutside("layout: the start square is outside the board".to_string()));
+		}
+		let mut squares = Vec::with_capacity(side * side);
+		for symbol in premiums.chars() {
+			match BoardTile::from_symbol(symbol) {
+				None => return Err(UnknownSymbol(format!("layout: unknown symbol {}", symbol))),
+				Some(premium) => squares.push(premium)
+			};
+		}
+		if squares.len() != side * side {
+			return Err(WrongSize("layout: wrong number of squares".to_string()));
+		}
+		return Ok(Layout{side, premiums: squares, start, rack_size: RACK_SIZE, bingo_bonus: BINGO_BONUS});
+	}
+
+	/// Board without premium squares, starting at the center
+	pub fn plain(side: usize) -> Result<Layout, LayoutError> {
+		return Layout::new(side, &"_".repeat(side * side), (side / 2, side / 2));
+	}
+
+	pub fn preset(name: &str) -> Option<Layout> {
+		let (_, rows, bingo_bonus) = PRESETS.iter().find(|(preset, _, _)| preset.eq_ignore_ascii_case(name))?;
+		let mut layout = Layout::new(rows.len(), &rows.concat(), (rows.len() / 2, rows.len() / 2)).unwrap();
+		layout.bingo_bonus = *bingo_bonus;
+		return Some(layout);
+	}
+
+	pub fn preset_names() -> Vec<&'static str> {
+		return PRESETS.iter().map(|(name, _, _)| *name).collect();
+	}
+
+	/// Layout written as text, the start square being the center if not given
+	pub fn parse(text: &str) -> Result<Layout, LayoutError> {
+		let mut rows: Vec<String> = Vec::new();
+		let mut start = None;
+		let mut rack_size = RACK_SIZE;
+		let mut bingo_bonus = BINGO_BONUS;
+		for (line_nb, line) in text.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+			let mut words = line.split_whitespace();
+			let key = words.next().unwrap();
+			let values: Result<Vec<usize>, _> = words.map(|value| value.parse::<usize>()).collect();
+			let wrong_metadata = || WrongMetadata(format!("layout: line {}: {}", line_nb + 1, line));
+			match (key, values) {
+				("start", Ok(values)) if values.len() == 2 => start = Some((values[0], values[1])),
+				("rack", Ok(values)) if values.len() == 1 => rack_size = values[0],
+				("bingo", Ok(values)) if values.len() == 1 => bingo_bonus = values[0],
+				("start", _) | ("rack", _) | ("bingo", _) => return Err(wrong_metadata()),
+				_ => rows.push(line.chars().filter(|c| !c.is_whitespace()).collect())
+			};
+		}
+
+		let side = rows.len();
+		if rows.iter().any(|row| row.chars().count() != side) {
+			return Err(WrongSize("layout: the grid is not square".to_string()));
+		}
+		if rack_size == 0 || rack_size > MAX_SIDE {
+			return Err(WrongMetadata(format!("layout: the rack size must be between 1 and {}", MAX_SIDE)));
+		}
+		let mut layout = Layout::new(side, &rows.concat(), start.unwrap_or((side / 2, side / 2)))?;
+		layout.rack_size = rack_size;
+		layout.bingo_bonus = bingo_bonus;
+		return Ok(layout);
+	}
+
+	pub fn from_file(filename: &str) -> Result<Layout, LayoutError> {
+		match std::fs::read_to_string(filename) {
+			Err(e) => return Err(UnreadableFile(format!("layout: {}: {}", filename, e))),
+			Ok(text) => return Layout::parse(&text)
+		};
+	}
+
+	/// Text of the layout, as read by `parse`
+	pub fn to_text(&self) -> String {
+		let mut text = format!("start {} {}\nrack {}\nbingo {}\n", self.start.0, self.start.1, self.rack_size, self.bingo_bonus);
+		for row in self.premiums.chunks(self.side) {
+			text.extend(row.iter().map(|premium| premium.symbol()));
+			text.push('\n');
+		}
+		return text;
+	}
+
+	pub fn side(&self) -> usize {
+		return self.side;
+	}
+
+	pub fn start(&self) -> (usize, usize) {
+		return self.start;
+	}
+
+	pub fn rack_size(&self) -> usize {
+		return self.rack_size;
+	}
+
+	pub fn bingo_bonus(&self) -> usize {
+		return self.bingo_bonus;
+	}
+
+	pub fn premium(&self, x: usize, y: usize) -> BoardTile {
+		return self.premiums[x * self.side + y];
+	}
+}
+
+#[pymethods]
+impl Layout {
+	/// `premiums` uses the symbols of the board messages, one per square
+	#[new]
+	fn py_new(side: usize, premiums: &str, start: (usize, usize)) -> pyo3::PyResult<Self> {
+		return Ok(Layout::new(side, premiums, start)?);
+	}
+
+	#[staticmethod]
+	#[pyo3(name = "plain")]
+	fn py_plain(side: usize) -> pyo3::PyResult<Self> {
+		return Ok(Layout::plain(side)?);
+	}
+
+	#[staticmethod]
+	#[pyo3(name = "preset")]
+	fn py_preset(name: &str) -> pyo3::PyResult<Self> {
+		match Layout::preset(name) {
+			None => return Err(pyo3::PyErr::new::<PyValueError, _>(format!("Layout: unknown preset {}", name))),
+			Some(layout) => return Ok(layout)
+		};
+	}
+
+	#[staticmethod]
+	fn presets() -> Vec<&'static str> {
+		return Layout::preset_names();
+	}
+
+	#[staticmethod]
+	#[pyo3(name = "parse")]
+	fn py_parse(text: &str) -> pyo3::PyResult<Self> {
+		return Ok(Layout::parse(text)?);
+	}
+
+	#[staticmethod]
+	#[pyo3(name = "from_file")]
+	fn py_from_file(filename: &str) -> pyo3::PyResult<Self> {
+		return Ok(Layout::from_file(filename)?);
+	}
+
+	fn __str__(&self) -> String {
+		return self.to_text();
+	}
+
+	#[pyo3(name = "side")]
+	fn py_side(&self) -> usize {
+		return self.side();
+	}
+
+	#[pyo3(name = "start")]
+	fn py_start(&self) -> (usize, usize) {
+		return self.start();
+	}
+
+	#[pyo3(name = "rack_size")]
+	fn py_rack_size(&self) -> usize {
+		return self.rack_size();
+	}
+
+	#[pyo3(name = "bingo_bonus")]
+	fn py_bingo_bonus(&self) -> usize {
+		return self.bingo_bonus();
+	}
+
+	/// Premium squares as a board message, to which the squares written `_` of a board message default
+	fn premiums(&self) -> String {
+		return self.premiums.iter().map(|premium| premium.symbol()).collect();
+	}
+}
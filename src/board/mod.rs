@@ -1,20 +1,68 @@
+// The board itself, with the errors and traits of the module around it
+#[allow(clippy::module_inception)]
 mod board;
-use board::Board;
+pub use board::Board;
 
-mod values;
 mod tile;
 
+mod layout;
+pub use layout::Layout;
+
+mod breakdown;
+pub use breakdown::ScoreBreakdown;
+
 pub use crate::constraints::WordToFill;
 pub use crate::constraints::PotentialWordConditionsBuilder;
+pub use crate::constraints::LineSquare;
 
-pub const SIDE: usize = 15;
-pub const SIZE: usize = SIDE * SIDE;
+/// Side of the largest board a layout can have
+pub const MAX_SIDE: usize = 21;
 
+/// Problem of a board message. Rows and columns count from 1, in the order of the squares of the message.
 #[derive(Debug)]
 #[derive(PartialEq)]
 pub enum DeserializingError {
+	/// Character, or tile between brackets, that is neither a tile of the alphabet nor a square symbol
+	UnknownSymbol{symbol: String, row: usize, column: usize},
+	/// Number of squares of the board, and of the message
+	WrongLength{expected: usize, found: usize},
+	/// Number of rows of the board, and of the grid
+	WrongRowCount{expected: usize, found: usize},
+	/// Every problem of the message, when there are several
+	Several(Vec<DeserializingError>)
+}
+
+impl DeserializingError {
+	pub fn message(&self) -> String {
+		match self {
+			DeserializingError::UnknownSymbol{symbol, row, column} =>
+				return format!("deserialize: unknown symbol {} at row {}, column {}", symbol, row, column),
+			DeserializingError::WrongLength{expected, found} =>
+				return format!("deserialize: {} squares instead of {}", found, expected),
+			DeserializingError::WrongRowCount{expected, found} =>
+				return format!("deserialize: {} rows instead of {}", found, expected),
+			DeserializingError::Several(problems) =>
+				return problems.iter().map(|problem| problem.message()).collect::<Vec<String>>().join("; ")
+		};
+	}
+
+	/// Problems one by one
+	pub fn problems(&self) -> Vec<&DeserializingError> {
+		match self {
+			DeserializingError::Several(problems) => return problems.iter().collect(),
+			problem => return vec![problem]
+		};
+	}
+}
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum LayoutError {
+	WrongSize(String),
 	UnknownSymbol(String),
-	WrongLength(String)
+	StartOutside(String),
+	WrongMetadata(String),
+	UnreadableFile(String)
 }
 
 #[derive(Debug)]
@@ -23,9 +71,59 @@ pub enum WordError {
 	TileOccupied(String),
 	UnexpectedUnderscore(String),
 	UnknownChar(String),
-	UnknownConstraint(String)
+	UnknownConstraint(String),
+	OutOfBoard(String),
+	MissingTile(String)
 } 
 
+/// Rule broken by a move, or `Scoring` when the move could not be scored
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum MoveError {
+	OutOfBoard(String),
+	TileOccupied(String),
+	NoTilePlaced(String),
+	NotContiguous(String),
+	NotConnected(String),
+	StartNotCovered(String),
+	NotInRack(String),
+	NotAWord(String),
+	Scoring(WordError)
+}
+
+/// Problem of a position found by `Board::check`. Coordinates are (row, column) from 0, as in moves.
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum BoardProblem {
+	/// Tiles are on the board, but not on the start square
+	StartEmpty,
+	/// Word as written with the alphabet, with its first square
+	NotAWord{word: String, coord: (usize, usize), vertical: bool},
+	/// Squares of a group of tiles apart from the one on the start square, or from the largest one
+	Disconnected(Vec<(usize, usize)>),
+	/// Tile as written with the alphabet, `0` for the blanks, more times on the board than in the tile set
+	TooManyTiles{tile: String, count: usize, available: usize}
+}
+
+impl BoardProblem {
+	pub fn message(&self) -> String {
+		// Squares are named as in the notation of moves
+		let name = |(x, y): (usize, usize)| format!("{}{}", x + 1, (b'A' + y as u8) as char);
+		match self {
+			BoardProblem::StartEmpty => return "check: the start square is empty".to_string(),
+			BoardProblem::NotAWord{word, coord, vertical: false} =>
+				return format!("check: {} at {} is not a word", word, name(*coord)),
+			BoardProblem::NotAWord{word, coord, vertical: true} =>
+				return format!("check: {} at {}{} is not a word", word, (b'A' + coord.1 as u8) as char, coord.0 + 1),
+			BoardProblem::Disconnected(squares) =>
+				return format!("check: the tiles at {} are not connected to the others",
+					squares.iter().map(|square| name(*square)).collect::<Vec<String>>().join(" ")),
+			BoardProblem::TooManyTiles{tile, count, available} =>
+				return format!("check: {} tiles {} on the board, but {} in the set", count, tile, available)
+		};
+	}
+}
+
 pub mod transposition
 {
 	pub struct Transposed;
@@ -42,13 +140,37 @@ pub mod transposition
 }
 
 pub trait BoardService {
+	/// Compact message of the board: one symbol per square, row by row, with no separator.
+	/// It is read back exactly by `deserialize` as long as the premiums under the tiles are the ones of the layout.
 	fn serialize<T: transposition::TransposedState>(&self) -> String;
-	fn deserialize(message: &str) -> Result<Board, DeserializingError>;
+	/// Grid of the symbols, with the rows numbered from 1 and the columns lettered from A
+	fn pretty<T: transposition::TransposedState>(&self) -> String;
+	/// Board of a compact message, or of a grid whose labels are optional
+	// Only the tests read boards with the default alphabet and layout, the others use `deserialize_with`
+	#[allow(dead_code)]
+	fn deserialize<T: transposition::TransposedState>(message: &str) -> Result<Board, DeserializingError>;
 	fn get_conditions<T: transposition::TransposedState, PWCB>(&self, x: usize, y: usize, conditions: &mut PWCB)
 	where PWCB: PotentialWordConditionsBuilder;
+	/// Score of a word, `_` being a tile already on the board. The tiles on the board count their face value
+	/// in every word they are part of, and premiums apply only to the new tiles.
 	fn get_score<T: transposition::TransposedState>(&self, word: &[char], x: usize, y: usize) -> Result<usize, WordError>;
+	fn get_score_breakdown<T: transposition::TransposedState>(&self, word: &[char], x: usize, y: usize) 
+	-> Result<ScoreBreakdown, WordError>;
+	fn get_line<T: transposition::TransposedState>(&self, x: usize, line: &mut Vec<LineSquare>);
+	fn is_empty(&self) -> bool;
+	fn layout(&self) -> &Layout;
 }
 
+// Only used by the tests, see `BoardService::deserialize`
+#[allow(dead_code)]
 pub fn deserialize(message: &str) -> Result<Board, DeserializingError> {
-	return Board::deserialize(message.clone());
+	return Board::deserialize::<transposition::NotTransposed>(message);
+}
+
+pub fn deserialize_with(
+	message: &str, 
+	alphabet: &std::sync::Arc<crate::alphabet::Alphabet>, 
+	layout: &std::sync::Arc<Layout>) 
+-> Result<Board, DeserializingError> {
+	return Board::deserialize_with(message, alphabet, layout);
 }
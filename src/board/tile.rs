@@ -1,6 +1,9 @@
+use crate::alphabet::to_blank;
+
 #[derive(Copy)]
 #[derive(Clone)]
 #[derive(Debug)]
+#[derive(PartialEq)]
 pub enum PlayedTile {
 	LetterTile(char),
 	JokerTile(char)
@@ -8,12 +11,35 @@ pub enum PlayedTile {
 #[derive(Copy)]
 #[derive(Clone)]
 #[derive(Debug)]
+#[derive(PartialEq)]
+// Variants are named after the squares of the board
+#[allow(clippy::enum_variant_names)]
 pub enum BoardTile {
 	EmptyTile,
 	LetterBonusTile(u8),
 	WordBonusTile(u8)
 }
 
+impl BoardTile {
+	/// Premium of a board message symbol: `2` to `4` multiply a letter, `5` to `7` multiply a word by the symbol minus 3
+	pub fn from_symbol(symbol: char) -> Option<BoardTile> {
+		match symbol {
+			'_' => Some(BoardTile::EmptyTile),
+			'2'..='4' => Some(BoardTile::LetterBonusTile(symbol as u8 - b'0')),
+			'5'..='7' => Some(BoardTile::WordBonusTile(symbol as u8 - b'3')),
+			_ => None
+		}
+	}
+
+	pub fn symbol(&self) -> char {
+		match self {
+			BoardTile::EmptyTile => '_',
+			BoardTile::LetterBonusTile(n) => (b'0' + n) as char,
+			BoardTile::WordBonusTile(n) => (b'3' + n) as char
+		}
+	}
+}
+
 #[derive(Copy)]
 #[derive(Clone)]
 #[derive(Debug)]
@@ -24,16 +50,13 @@ pub enum Tile{
 
 impl Tile {
 	pub fn is_occupied(&self) -> bool {
-		match self.letter() {
-			None => false,
-			Some(_) => true
-		}
+		return self.letter().is_some();
 	}
 
 	pub fn letter(&self) -> Option<char> {
 		match self {
 			Tile::Played(PlayedTile::LetterTile(c)) => Some(*c),
-			Tile::Played(PlayedTile::JokerTile(c)) => Some(c.to_ascii_uppercase()),
+			Tile::Played(PlayedTile::JokerTile(c)) => Some(to_blank(*c)),
 			_ => None
 		}
 	}
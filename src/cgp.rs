@@ -0,0 +1,270 @@
+use crate::alphabet::{Alphabet, swap_case};
+use crate::board::{Board, Layout, DeserializingError, MAX_SIDE};
+use CgpError::*;
+
+use std::sync::Arc;
+
+use pyo3::prelude::{pyclass, pymethods};
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum CgpError {
+	Syntax(String),
+	WrongBoard(String),
+	UnknownTile(String)
+}
+
+/// Game position in the CGP format: `<rows> <racks> <scores> <zero turns> [<opcode> <value>; ...]`.
+/// Rows are separated by `/`, a number being a run of empty squares, regular tiles are uppercase
+/// and blanks lowercase. Racks and scores are separated by `/`, the player on turn first, `?` being a blank.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[pyclass]
+pub struct Position {
+	alphabet: Alphabet,
+	side: usize,
+	/// Letter of each square, row by row, blanks in uppercase
+	squares: Vec<Option<char>>,
+	/// Premium symbol of each empty square of a board message, none for a CGP text
+	premiums: Vec<Option<char>>,
+	/// Racks written with the letters of the alphabet, `0` being a blank
+	pub racks: Vec<String>,
+	pub scores: Vec<isize>,
+	/// Number of turns in a row without points
+	pub zero_turns: usize,
+	/// Opcodes, such as `lex`, with their value
+	pub options: Vec<(String, String)>
+}
+
+impl Position {
+	pub fn parse(text: &str, alphabet: &Alphabet) -> Result<Position, CgpError> {
+		let mut tokens = text.split_whitespace();
+		let (rows, racks, scores, zero_turns) = match (tokens.next(), tokens.next(), tokens.next(), tokens.next()) {
+			(Some(rows), Some(racks), Some(scores), Some(zero_turns)) => (rows, racks, scores, zero_turns),
+			_ => return Err(Syntax("cgp: expected the board, the racks, the scores and the number of zero turns".to_string()))
+		};
+
+		let rows: Vec<&str> = rows.split('/').collect();
+		let side = rows.len();
+		if side > MAX_SIDE {
+			return Err(WrongBoard(format!("cgp: more than {} rows", MAX_SIDE)));
+		}
+		let mut position = Position::empty(side, alphabet);
+		position.squares.clear();
+		for (row_nb, row) in rows.iter().enumerate() {
+			let mut squares = Vec::with_capacity(side);
+			let mut chars = row.chars().peekable();
+			while let Some(c) = chars.next() {
+				if let Some(digit) = c.to_digit(10) {
+					let mut empty = digit as usize;
+					while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+						empty = empty * 10 + digit as usize;
+						chars.next();
+					}
+					squares.extend(std::iter::repeat_n(None, empty));
+					continue;
+				}
+				let text: String = match c {
+					'[' => chars.by_ref().take_while(|c| *c != ']').collect(),
+					c => c.to_string()
+				};
+				match alphabet.parse_tile(&swap_case(&text)) {
+					None => return Err(UnknownTile(format!("cgp: row {}: unknown tile {}", row_nb + 1, text))),
+					Some(c) => squares.push(Some(c))
+				};
+			}
+			if squares.len() != side {
+				return Err(WrongBoard(format!("cgp: row {} has {} squares instead of {}", row_nb + 1, squares.len(), side)));
+			}
+			position.squares.extend(squares);
+		}
+
+		position.racks = racks.split('/').map(|rack| position.encode_rack(rack)).collect::<Result<_, _>>()?;
+		position.scores = scores.split('/')
+			.map(|score| score.parse::<isize>().map_err(|_| Syntax(format!("cgp: wrong score {}", score))))
+			.collect::<Result<_, _>>()?;
+		position.zero_turns = zero_turns.parse::<usize>().map_err(|_| Syntax(format!("cgp: wrong number of zero turns {}", zero_turns)))?;
+		for option in tokens.collect::<Vec<&str>>().join(" ").split(';') {
+			let option = option.trim();
+			if option.is_empty() {
+				continue;
+			}
+			let (key, value) = option.split_once(' ').unwrap_or((option, ""));
+			position.options.push((key.to_string(), value.trim().to_string()));
+		}
+		return Ok(position);
+	}
+
+	/// Position of a board message, as read by `deserialize`, with no racks, no scores and no options.
+	/// The premium squares of the message are kept, and override the layout in `board`,
+	/// but the CGP text has no premiums.
+	pub fn from_message(message: &str, alphabet: &Alphabet) -> Result<Position, CgpError> {
+		let mut squares = Vec::new();
+		let mut premiums = Vec::new();
+		let mut chars = message.chars();
+		while let Some(c) = chars.next() {
+			// Plain and premium squares
+			if c == '_' || ('2'..='7').contains(&c) {
+				squares.push(None);
+				premiums.push(Some(c).filter(|c| *c != '_'));
+				continue;
+			}
+			premiums.push(None);
+			let text: String = match c {
+				'[' => chars.by_ref().take_while(|c| *c != ']').collect(),
+				c => c.to_string()
+			};
+			match alphabet.parse_tile(&text) {
+				None => return Err(UnknownTile(format!("cgp: unknown tile {}", text))),
+				Some(c) => squares.push(Some(c))
+			};
+		}
+		let side = (1..=MAX_SIDE).find(|side| side * side == squares.len())
+			.ok_or_else(|| WrongBoard(format!("cgp: {} squares don't make a board", squares.len())))?;
+		let mut position = Position::empty(side, alphabet);
+		position.squares = squares;
+		position.premiums = premiums;
+		return Ok(position);
+	}
+
+	fn empty(side: usize, alphabet: &Alphabet) -> Position {
+		return Position{
+			alphabet: alphabet.clone(),
+			side,
+			squares: vec![None; side * side],
+			premiums: Vec::new(),
+			racks: vec![String::new(), String::new()],
+			scores: vec![0, 0],
+			zero_turns: 0,
+			options: Vec::new()};
+	}
+
+	/// Board message of the position, an empty square being `_` or the premium symbol it was read with
+	pub fn message(&self) -> String {
+		return self.squares.iter().enumerate().map(|(idx, square)| match (square, self.premiums.get(idx)) {
+			(Some(c), _) => self.alphabet.display(*c).unwrap(),
+			(None, Some(Some(premium))) => premium.to_string(),
+			(None, _) => "_".to_string()
+		}).collect();
+	}
+
+	pub fn board(&self, layout: &Arc<Layout>) -> Result<Board, DeserializingError> {
+		return Board::deserialize_with(&self.message(), &Arc::new(self.alphabet.clone()), layout);
+	}
+
+	/// Rack of the player on turn
+	pub fn rack(&self) -> &str {
+		return self.racks.first().map_or("", |rack| rack.as_str());
+	}
+
+	pub fn option(&self, key: &str) -> Option<&str> {
+		return self.options.iter().find(|(option, _)| option == key).map(|(_, value)| value.as_str());
+	}
+
+	pub fn to_text(&self) -> String {
+		let mut rows = Vec::with_capacity(self.side);
+		for row in self.squares.chunks(self.side) {
+			let mut text = String::new();
+			let mut empty = 0;
+			for square in row {
+				match square {
+					None => empty += 1,
+					Some(c) => {
+						if empty > 0 {
+							text.push_str(&empty.to_string());
+							empty = 0;
+						}
+						text.push_str(&swap_case(&self.alphabet.display(*c).unwrap()));
+					}
+				};
+			}
+			if empty > 0 {
+				text.push_str(&empty.to_string());
+			}
+			rows.push(text);
+		}
+		let racks: Vec<String> = self.racks.iter().map(|rack| self.decode_rack(rack)).collect();
+		let scores: Vec<String> = self.scores.iter().map(|score| score.to_string()).collect();
+		let mut text = format!("{} {} {} {}", rows.join("/"), racks.join("/"), scores.join("/"), self.zero_turns);
+		for (key, value) in &self.options {
+			match value.is_empty() {
+				true => text.push_str(&format!(" {};", key)),
+				false => text.push_str(&format!(" {} {};", key, value))
+			};
+		}
+		return text;
+	}
+
+	// Racks are written in uppercase, `?` being a blank
+	fn encode_rack(&self, rack: &str) -> Result<String, CgpError> {
+		match self.alphabet.encode(rack, |c| c == '?') {
+			Err(_) => return Err(UnknownTile(format!("cgp: unknown tile in the rack {}", rack))),
+			Ok(rack) => return Ok(rack.replace('?', "0"))
+		};
+	}
+
+	fn decode_rack(&self, rack: &str) -> String {
+		return self.alphabet.decode(rack).to_uppercase().replace('0', "?");
+	}
+}
+
+#[pymethods]
+impl Position {
+	#[staticmethod]
+	#[args(alphabet = "None")]
+	#[pyo3(name = "parse")]
+	fn py_parse(text: &str, alphabet: Option<Alphabet>) -> pyo3::PyResult<Self> {
+		return Ok(Position::parse(text, &alphabet.unwrap_or_default())?);
+	}
+
+	/// Position of a board message, with the racks written as for a `WordFinder`, the player on turn first
+	#[staticmethod]
+	#[args(racks = "None", scores = "None", alphabet = "None")]
+	#[pyo3(name = "from_message")]
+	fn py_from_message(message: &str, racks: Option<Vec<String>>, scores: Option<Vec<isize>>, alphabet: Option<Alphabet>)
+	-> pyo3::PyResult<Self> {
+		let mut position = Position::from_message(message, &alphabet.unwrap_or_default())?;
+		if let Some(racks) = racks {
+			position.racks = racks.iter()
+				.map(|rack| position.encode_rack(&rack.replace('0', "?")))
+				.collect::<Result<_, _>>()?;
+		}
+		if let Some(scores) = scores {
+			position.scores = scores;
+		}
+		return Ok(position);
+	}
+
+	fn __str__(&self) -> String {
+		return self.to_text();
+	}
+
+	#[pyo3(name = "message")]
+	fn py_message(&self) -> String {
+		return self.message();
+	}
+
+	#[args(layout = "None")]
+	#[pyo3(name = "board")]
+	fn py_board(&self, layout: Option<Layout>) -> pyo3::PyResult<Board> {
+		return Ok(self.board(&Arc::new(layout.unwrap_or_default()))?);
+	}
+
+	/// Racks written as in the CGP format
+	fn racks(&self) -> Vec<String> {
+		return self.racks.iter().map(|rack| self.decode_rack(rack)).collect();
+	}
+
+	fn scores(&self) -> Vec<isize> {
+		return self.scores.clone();
+	}
+
+	fn zero_turns(&self) -> usize {
+		return self.zero_turns;
+	}
+
+	fn options(&self) -> Vec<(String, String)> {
+		return self.options.clone();
+	}
+}
@@ -15,7 +15,7 @@ pub struct WordToFill {
 pub struct NoWordToFillError;
 impl WordToFill {
 	pub fn new(begin: String, end: String) -> Result<Self, NoWordToFillError> {
-		if begin == "" && end == "" {
+		if begin.is_empty() && end.is_empty() {
 			return Err(NoWordToFillError);
 		}
 		return Ok(Self{beginning: begin.clone(), end: end.clone()});
@@ -62,7 +62,7 @@ impl ConstraintWords for CW {
 					_ => None
 				};
 
-				for (idx, _) in vec.into_iter() {
+				for (idx, _) in vec.iter_mut() {
 					*idx -= 1;
 				}
 				return ret;
@@ -94,7 +94,7 @@ impl ConstraintNbLetters for CNbL {
 					Some(&0) => {vec.pop(); true}
 					_ => false
 				};
-				for el in vec.into_iter() {
+				for el in vec.iter_mut() {
 					*el -= 1;
 				}
 				ret
@@ -128,7 +128,7 @@ impl ConstraintLetters for CL {
 					_ => None
 				};
 
-				for (idx, _) in vec.into_iter() {
+				for (idx, _) in vec.iter_mut() {
 					*idx -= 1;
 				}
 				return ret;
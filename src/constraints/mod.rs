@@ -1,7 +1,19 @@
+// The constraints themselves, with their builders around them
+#[allow(clippy::module_inception)]
 mod constraints;
 pub use constraints::WordToFill;
 pub use constraints::PotentialWord;
 
+/// Content of one square of a board line, as seen when growing words from anchor squares.
+/// An anchor is an empty square next to a played tile: every move has to cover one.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum LineSquare {
+	Letter(char),
+	Empty{anchor: bool, cross_word: Option<WordToFill>}
+}
+
 pub trait ConstraintNbLetters: Clone {
 	fn sort_and_fuse(&mut self);
 	fn decrease(&mut self) -> bool;
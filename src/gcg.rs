@@ -0,0 +1,350 @@
+use crate::alphabet::Alphabet;
+use crate::board::{Board, Layout};
+use crate::solver::Move;
+use GcgError::*;
+
+use std::sync::Arc;
+
+use pyo3::prelude::{pyclass, pymethods};
+
+/// Player, rack, action, score and total of a turn
+type TurnText = (String, String, String, isize, isize);
+
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum GcgError {
+	Syntax(String),
+	UnknownPlayer(String),
+	IllegalMove(String),
+	ScoreMismatch(String),
+	UnreadableFile(String)
+}
+
+/// What a player did on a turn. Tiles are written with the letters of the alphabet, `0` being a blank.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum Action {
+	Play(Box<Move>),
+	Exchange(String),
+	/// Exchange of which only the number of tiles is known
+	ExchangeCount(usize),
+	Pass,
+	/// The previous move of the player was challenged off the board
+	PhonyWithdrawn,
+	/// Bonus for a move wrongly challenged
+	ChallengeBonus,
+	TimePenalty,
+	/// Points of the tiles left on the rack of the opponent, for going out
+	EndRack(String),
+	/// Points of the tiles left on the rack of the player, at the end of the game
+	RackPenalty(String)
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct Turn {
+	pub player: String,
+	/// Rack before the turn, empty if not recorded
+	pub rack: String,
+	pub action: Action,
+	pub score: isize,
+	/// Score of the player after the turn
+	pub total: isize,
+	pub notes: Vec<String>
+}
+
+/// Game record in the GCG format: `#` pragma lines, among which `#player1 <nick> <name>`, `#player2`
+/// and `#note`, then one `>nick: ...` line per turn. Notes go with the turn before them.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[pyclass]
+pub struct Game {
+	alphabet: Alphabet,
+	/// Nick and full name of each player
+	pub players: Vec<(String, String)>,
+	/// Pragmas other than the players and the notes, in their order
+	pub headers: Vec<(String, String)>,
+	/// Notes given before the first turn
+	pub notes: Vec<String>,
+	pub turns: Vec<Turn>
+}
+
+impl Game {
+	pub fn parse(text: &str, alphabet: &Alphabet) -> Result<Game, GcgError> {
+		let mut game = Game{alphabet: alphabet.clone(), players: Vec::new(), headers: Vec::new(), notes: Vec::new(), turns: Vec::new()};
+		let mut in_note = false;
+		for (line_nb, line) in text.lines().enumerate() {
+			let syntax_error = || Syntax(format!("gcg: line {}: {}", line_nb + 1, line));
+			if let Some(turn) = line.strip_prefix('>') {
+				in_note = false;
+				let turn = game.parse_turn(turn).ok_or_else(syntax_error)?;
+				if !game.players.is_empty() && !game.players.iter().any(|(nick, _)| *nick == turn.player) {
+					return Err(UnknownPlayer(format!("gcg: line {}: unknown player {}", line_nb + 1, turn.player)));
+				}
+				game.turns.push(turn);
+			} else if let Some(pragma) = line.strip_prefix('#') {
+				in_note = false;
+				let (key, value) = match pragma.split_once(char::is_whitespace) {
+					None => (pragma, ""),
+					Some((key, value)) => (key, value.trim())
+				};
+				match key {
+					"player1" | "player2" => {
+						let (nick, name) = value.split_once(char::is_whitespace).unwrap_or((value, ""));
+						if nick.is_empty() {
+							return Err(syntax_error());
+						}
+						game.players.push((nick.to_string(), name.trim().to_string()));
+					},
+					"note" => {
+						in_note = true;
+						match game.turns.last_mut() {
+							None => game.notes.push(value.to_string()),
+							Some(turn) => turn.notes.push(value.to_string())
+						};
+					},
+					_ => game.headers.push((key.to_string(), value.to_string()))
+				};
+			} else if in_note && !line.trim().is_empty() {
+				// A note goes on until the next pragma or turn
+				let notes = match game.turns.last_mut() {
+					None => &mut game.notes,
+					Some(turn) => &mut turn.notes
+				};
+				let note = notes.last_mut().unwrap();
+				note.push('\n');
+				note.push_str(line.trim());
+			} else if !line.trim().is_empty() {
+				return Err(syntax_error());
+			}
+		}
+		return Ok(game);
+	}
+
+	pub fn from_file(filename: &str, alphabet: &Alphabet) -> Result<Game, GcgError> {
+		match std::fs::read_to_string(filename) {
+			Err(e) => return Err(UnreadableFile(format!("gcg: {}: {}", filename, e))),
+			Ok(text) => return Game::parse(&text, alphabet)
+		};
+	}
+
+	// Turn line, without its leading `>`
+	fn parse_turn(&self, line: &str) -> Option<Turn> {
+		let (player, rest) = line.split_once(':')?;
+		let tokens: Vec<&str> = rest.split_whitespace().collect();
+		let (rack, tokens) = match tokens.first() {
+			Some(first) if first.starts_with('(') => ("", &tokens[..]),
+			Some(first) => (*first, &tokens[1..]),
+			None => return None
+		};
+		let (action, score, total) = match tokens {
+			[coordinate, word, score, total] => {
+				let mv = Move::from_notation(&format!("{} {}", coordinate, word), &self.alphabet).ok()?;
+				(Action::Play(Box::new(mv)), score, total)
+			},
+			[action, score, total] => {
+				let action = match *action {
+					"-" => Action::Pass,
+					"--" => Action::PhonyWithdrawn,
+					"(challenge)" => Action::ChallengeBonus,
+					"(time)" => Action::TimePenalty,
+					exchanged if exchanged.starts_with('-') => match exchanged[1..].parse::<usize>() {
+						Ok(count) => Action::ExchangeCount(count),
+						Err(_) => Action::Exchange(self.encode_rack(&exchanged[1..])?)
+					},
+					tiles if tiles.starts_with('(') && tiles.ends_with(')') => {
+						let tiles = self.encode_rack(&tiles[1..tiles.len()-1])?;
+						match rack {
+							"" => Action::EndRack(tiles),
+							_ => Action::RackPenalty(tiles)
+						}
+					},
+					_ => return None
+				};
+				(action, score, total)
+			},
+			_ => return None
+		};
+		return Some(Turn{
+			player: player.trim().to_string(),
+			rack: self.encode_rack(rack)?,
+			action,
+			score: score.parse::<isize>().ok()?,
+			total: total.parse::<isize>().ok()?,
+			notes: Vec::new()});
+	}
+
+	// Racks are written in uppercase, `?` being a blank
+	fn encode_rack(&self, rack: &str) -> Option<String> {
+		let rack = self.alphabet.encode(rack, |c| c == '?').ok()?;
+		return Some(rack.replace('?', "0"));
+	}
+
+	fn decode_rack(&self, rack: &str) -> String {
+		return self.alphabet.decode(rack).to_uppercase().replace('0', "?");
+	}
+
+	/// Text of the game in the GCG format
+	pub fn to_text(&self) -> Result<String, GcgError> {
+		// The encoding comes first, so that the rest of the file can be read
+		let (encoding, headers): (Vec<_>, Vec<_>) = self.headers.iter().partition(|(key, _)| key == "character-encoding");
+		let mut text = String::new();
+		for (key, value) in encoding {
+			text.push_str(&format!("#{} {}\n", key, value));
+		}
+		for (idx, (nick, name)) in self.players.iter().enumerate() {
+			text.push_str(&format!("#player{} {} {}\n", idx + 1, nick, name));
+		}
+		for (key, value) in headers {
+			text.push_str(&format!("#{} {}\n", key, value));
+		}
+		for note in &self.notes {
+			text.push_str(&format!("#note {}\n", note));
+		}
+		for turn in &self.turns {
+			let action = self.action_text(&turn.action)?;
+			let mut line = format!(">{}: ", turn.player);
+			if !turn.rack.is_empty() {
+				line.push_str(&self.decode_rack(&turn.rack));
+				line.push(' ');
+			}
+			text.push_str(&format!("{}{} {:+} {}\n", line, action, turn.score, turn.total));
+			for note in &turn.notes {
+				text.push_str(&format!("#note {}\n", note));
+			}
+		}
+		return Ok(text);
+	}
+
+	// Action as written in a turn line
+	fn action_text(&self, action: &Action) -> Result<String, GcgError> {
+		return Ok(match action {
+			Action::Play(mv) => match mv.to_notation(&self.alphabet) {
+				Err(_) => return Err(IllegalMove(format!("gcg: move {} can't be written", mv.word))),
+				Ok(notation) => notation
+			},
+			Action::Exchange(tiles) => format!("-{}", self.decode_rack(tiles)),
+			Action::ExchangeCount(count) => format!("-{}", count),
+			Action::Pass => "-".to_string(),
+			Action::PhonyWithdrawn => "--".to_string(),
+			Action::ChallengeBonus => "(challenge)".to_string(),
+			Action::TimePenalty => "(time)".to_string(),
+			Action::EndRack(tiles) | Action::RackPenalty(tiles) => format!("({})", self.decode_rack(tiles))
+		});
+	}
+
+	/// Plays the turns of the game on the board, checking the score of every move and the total of every turn
+	pub fn replay(&self, board: &mut Board) -> Result<(), GcgError> {
+		let mut totals: Vec<(&str, isize)> = Vec::new();
+		// Last move of each player with its score, taken back if withdrawn
+		let mut last_moves: Vec<(&str, &Move, isize)> = Vec::new();
+		for (idx, turn) in self.turns.iter().enumerate() {
+			let turn_nb = idx + 1;
+			match &turn.action {
+				Action::Play(mv) => {
+					let score = match board.apply_move(mv) {
+						Err(e) => return Err(IllegalMove(format!("gcg: turn {}: {:?}", turn_nb, e))),
+						Ok(score) => score as isize
+					};
+					if score != turn.score {
+						return Err(ScoreMismatch(format!("gcg: turn {}: the move scores {}, not {}", turn_nb, score, turn.score)));
+					}
+					last_moves.retain(|(player, _, _)| *player != turn.player);
+					last_moves.push((&turn.player, mv, score));
+				},
+				Action::PhonyWithdrawn => {
+					let (mv, score) = match last_moves.iter().position(|(player, _, _)| *player == turn.player) {
+						None => return Err(IllegalMove(format!("gcg: turn {}: no move to withdraw", turn_nb))),
+						Some(idx) => {
+							let (_, mv, score) = last_moves.remove(idx);
+							(mv, score)
+						}
+					};
+					if turn.score != -score {
+						return Err(ScoreMismatch(format!("gcg: turn {}: the withdrawn move scored {}, not {}", turn_nb, score, -turn.score)));
+					}
+					if let Err(e) = board.undo_move(mv) {
+						return Err(IllegalMove(format!("gcg: turn {}: {:?}", turn_nb, e)));
+					}
+				},
+				_ => ()
+			};
+
+			let previous = match totals.iter_mut().find(|(player, _)| *player == turn.player) {
+				Some(total) => total,
+				None => {
+					totals.push((&turn.player, 0));
+					totals.last_mut().unwrap()
+				}
+			};
+			if previous.1 + turn.score != turn.total {
+				return Err(ScoreMismatch(format!("gcg: turn {}: the total is {}, not {}", turn_nb, previous.1 + turn.score, turn.total)));
+			}
+			previous.1 = turn.total;
+		}
+		return Ok(());
+	}
+}
+
+#[pymethods]
+impl Game {
+	#[staticmethod]
+	#[args(alphabet = "None")]
+	#[pyo3(name = "parse")]
+	fn py_parse(text: &str, alphabet: Option<Alphabet>) -> pyo3::PyResult<Self> {
+		return Ok(Game::parse(text, &alphabet.unwrap_or_default())?);
+	}
+
+	#[staticmethod]
+	#[args(alphabet = "None")]
+	#[pyo3(name = "from_file")]
+	fn py_from_file(filename: &str, alphabet: Option<Alphabet>) -> pyo3::PyResult<Self> {
+		return Ok(Game::from_file(filename, &alphabet.unwrap_or_default())?);
+	}
+
+	fn __str__(&self) -> pyo3::PyResult<String> {
+		return Ok(self.to_text()?);
+	}
+
+	/// Board at the end of the game, after checking every score. The layout is the standard Scrabble one
+	/// unless given, and the letter values are the ones of the alphabet, or of the built-in tile set named
+	/// `tile_set`, or else of the alphabet the game was read with.
+	#[args(layout = "None", alphabet = "None", tile_set = "None")]
+	#[pyo3(name = "replay")]
+	fn py_replay(&self, layout: Option<Layout>, alphabet: Option<Alphabet>, tile_set: Option<String>) -> pyo3::PyResult<Board> {
+		let layout = match layout {
+			Some(layout) => layout,
+			None => Layout::preset("scrabble").unwrap()
+		};
+		// The moves are read again when their letters have other values
+		let reread;
+		let game = match crate::choose_alphabet(alphabet, tile_set)? {
+			Some(alphabet) if alphabet != self.alphabet => {
+				reread = Game::parse(&self.to_text()?, &alphabet)?;
+				&reread
+			},
+			_ => self
+		};
+		let mut board = Board::new_empty(&Arc::new(game.alphabet.clone()), &Arc::new(layout));
+		game.replay(&mut board)?;
+		return Ok(board);
+	}
+
+	#[pyo3(name = "players")]
+	fn py_players(&self) -> Vec<(String, String)> {
+		return self.players.clone();
+	}
+
+	/// Turns as (player, rack, action, score, total), written as in the GCG format
+	#[pyo3(name = "turns")]
+	fn py_turns(&self) -> pyo3::PyResult<Vec<TurnText>> {
+		let mut turns = Vec::with_capacity(self.turns.len());
+		for turn in &self.turns {
+			turns.push((turn.player.clone(), self.decode_rack(&turn.rack), self.action_text(&turn.action)?, turn.score, turn.total));
+		}
+		return Ok(turns);
+	}
+}
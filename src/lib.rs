@@ -3,24 +3,47 @@
 #[cfg(test)]
 mod test;
 
+mod alphabet;
+use alphabet::{Alphabet, AlphabetError};
+
+mod tile_set;
+use tile_set::TileSet;
+
 mod str_tree;
 use str_tree::Dictionnary;
+use str_tree::WordLookup;
+use str_tree::AnchoredDictionnary;
 
 mod constraints;
 
 mod board;
 use board::DeserializingError;
 use board::DeserializingError::*;
+use board::{Layout, LayoutError};
+use board::BoardService;
 use board::WordError;
 use board::WordError::*;
+use board::MoveError;
 
 mod solver;
 use solver::BestWord;
 use solver::WithoutTimer;
+use solver::MoveCursor;
+
+mod notation;
+use notation::NotationError;
+
+mod gcg;
+use gcg::{Game, GcgError};
+
+mod cgp;
+use cgp::{Position, CgpError};
 
 use pyo3::prelude::*;
 use pyo3::exceptions::PyValueError;
 
+use std::sync::Arc;
+
 trait ErrorTypeToString { fn str() -> String; }
 fn py_value_error<ErrorType: ErrorTypeToString>(msg: &str) -> pyo3::PyErr {
 	let mut string = ErrorType::str();
@@ -37,59 +60,552 @@ impl From<WordError> for pyo3::PyErr {
 			TileOccupied(s) => py_value_error::<WordError>(&s),
 			UnknownChar(s) => py_value_error::<WordError>(&s),
 			UnexpectedUnderscore(s) => py_value_error::<WordError>(&s),
-			UnknownConstraint(s) => py_value_error::<WordError>(&s)
+			UnknownConstraint(s) => py_value_error::<WordError>(&s),
+			OutOfBoard(s) => py_value_error::<WordError>(&s),
+			MissingTile(s) => py_value_error::<WordError>(&s)
+		}
+	}
+}
+
+impl ErrorTypeToString for MoveError { fn str() -> String {"MoveError".to_string()} }
+impl From<MoveError> for pyo3::PyErr {
+	fn from(e: MoveError) -> Self {
+		match e {
+			MoveError::OutOfBoard(s) => py_value_error::<MoveError>(&s),
+			MoveError::TileOccupied(s) => py_value_error::<MoveError>(&s),
+			MoveError::NoTilePlaced(s) => py_value_error::<MoveError>(&s),
+			MoveError::NotContiguous(s) => py_value_error::<MoveError>(&s),
+			MoveError::NotConnected(s) => py_value_error::<MoveError>(&s),
+			MoveError::StartNotCovered(s) => py_value_error::<MoveError>(&s),
+			MoveError::NotInRack(s) => py_value_error::<MoveError>(&s),
+			MoveError::NotAWord(s) => py_value_error::<MoveError>(&s),
+			MoveError::Scoring(e) => e.into()
+		}
+	}
+}
+
+impl ErrorTypeToString for NotationError { fn str() -> String {"NotationError".to_string()} }
+impl From<NotationError> for pyo3::PyErr {
+	fn from(e: NotationError) -> Self {
+		match e {
+			NotationError::WrongCoordinate(s) => py_value_error::<NotationError>(&s),
+			NotationError::WrongWord(s) => py_value_error::<NotationError>(&s)
+		}
+	}
+}
+
+impl ErrorTypeToString for GcgError { fn str() -> String {"GcgError".to_string()} }
+impl From<GcgError> for pyo3::PyErr {
+	fn from(e: GcgError) -> Self {
+		match e {
+			GcgError::Syntax(s) => py_value_error::<GcgError>(&s),
+			GcgError::UnknownPlayer(s) => py_value_error::<GcgError>(&s),
+			GcgError::IllegalMove(s) => py_value_error::<GcgError>(&s),
+			GcgError::ScoreMismatch(s) => py_value_error::<GcgError>(&s),
+			GcgError::UnreadableFile(s) => py_value_error::<GcgError>(&s)
+		}
+	}
+}
+
+impl ErrorTypeToString for CgpError { fn str() -> String {"CgpError".to_string()} }
+impl From<CgpError> for pyo3::PyErr {
+	fn from(e: CgpError) -> Self {
+		match e {
+			CgpError::Syntax(s) => py_value_error::<CgpError>(&s),
+			CgpError::WrongBoard(s) => py_value_error::<CgpError>(&s),
+			CgpError::UnknownTile(s) => py_value_error::<CgpError>(&s)
+		}
+	}
+}
+
+impl ErrorTypeToString for AlphabetError { fn str() -> String {"AlphabetError".to_string()} }
+impl From<AlphabetError> for pyo3::PyErr {
+	fn from(e: AlphabetError) -> Self {
+		match e {
+			AlphabetError::InvalidTile(s) => py_value_error::<AlphabetError>(&s),
+			AlphabetError::TooManyTiles(s) => py_value_error::<AlphabetError>(&s)
+		}
+	}
+}
+
+impl ErrorTypeToString for LayoutError { fn str() -> String {"LayoutError".to_string()} }
+impl From<LayoutError> for pyo3::PyErr {
+	fn from(e: LayoutError) -> Self {
+		match e {
+			LayoutError::WrongSize(s) => py_value_error::<LayoutError>(&s),
+			LayoutError::UnknownSymbol(s) => py_value_error::<LayoutError>(&s),
+			LayoutError::StartOutside(s) => py_value_error::<LayoutError>(&s),
+			LayoutError::WrongMetadata(s) => py_value_error::<LayoutError>(&s),
+			LayoutError::UnreadableFile(s) => py_value_error::<LayoutError>(&s)
 		}
 	}
 }
 
-impl ErrorTypeToString for DeserializingError { fn str() -> String {"DeserializingError".to_string()} }
+mod exceptions {
+	// Raised for a wrong board message. `unknown_symbols` holds a (symbol, row, column) tuple for each unknown symbol,
+	// `expected_length` and `found_length` are None unless the number of squares is wrong,
+	// `expected_rows` and `found_rows` None unless a grid has a wrong number of rows,
+	// and `problems` has the message of each problem.
+	pyo3::create_exception!(rsScrabble, DeserializingError, pyo3::exceptions::PyValueError);
+}
+
 impl From<DeserializingError> for pyo3::PyErr {
 	fn from(e: DeserializingError) -> Self {
-		match e {
-			WrongLength(s) => py_value_error::<DeserializingError>(&s),
-			UnknownSymbol(s) => py_value_error::<DeserializingError>(&s)
+		let mut unknown_symbols = Vec::new();
+		let mut lengths = None;
+		let mut rows = None;
+		for problem in e.problems() {
+			match problem {
+				UnknownSymbol{symbol, row, column} => unknown_symbols.push((symbol.clone(), *row, *column)),
+				WrongLength{expected, found} => lengths = Some((*expected, *found)),
+				WrongRowCount{expected, found} => rows = Some((*expected, *found)),
+				Several(_) => ()
+			};
 		}
+		let problems: Vec<String> = e.problems().iter().map(|problem| problem.message()).collect();
+
+		let err = PyErr::new::<exceptions::DeserializingError, _>(e.message());
+		Python::with_gil(|py| -> PyResult<()> {
+			let instance = err.instance(py);
+			instance.setattr("unknown_symbols", unknown_symbols)?;
+			instance.setattr("expected_length", lengths.map(|(expected, _)| expected))?;
+			instance.setattr("found_length", lengths.map(|(_, found)| found))?;
+			instance.setattr("expected_rows", rows.map(|(expected, _)| expected))?;
+			instance.setattr("found_rows", rows.map(|(_, found)| found))?;
+			instance.setattr("problems", problems)?;
+			return Ok(());
+		}).expect("DeserializingError: attributes not set");
+		return err;
 	}
 }
 
 #[pyclass]
 struct WordFinder {
-	_tree: str_tree::StrTree,
-	_word_buffer: Vec<str_tree::StaticWord>
+	_dict: str_tree::Lexicon,
+	_word_buffer: Vec<str_tree::StaticWord>,
+	_alphabet: Arc<Alphabet>,
+	_layout: Arc<Layout>
 }
 
 #[pymethods]
 impl WordFinder {
+	/// `blacklist` is an optional file of words to remove from the lexicon once loaded.
+	/// Words, racks and boards are written with the tiles of `alphabet`, the latin letters by default.
+	/// `tile_set` is instead the name of a built-in set, such as "english", whose values are then used for scoring.
+	/// `layout` gives the size, premium squares and start square of the boards, a plain 15×15 board by default.
+	/// Board messages then only need the played tiles, their `_` squares taking the premiums of the layout.
 	#[new]
-	fn new(filename: &str) -> PyResult<Self> {
-		match str_tree::build_dict_from_file(filename) {
+	#[args(blacklist = "None", alphabet = "None", tile_set = "None", layout = "None")]
+	fn new(filename: &str, blacklist: Option<String>, alphabet: Option<Alphabet>, tile_set: Option<String>, layout: Option<Layout>) -> PyResult<Self> {
+		let alphabet = choose_alphabet(alphabet, tile_set)?;
+		let tree = match &alphabet {
+			None => str_tree::build_dict_from_file(filename),
+			Some(alphabet) => str_tree::build_dict_with_alphabet(filename, alphabet)
+		};
+		match tree {
+			Err(e) => Err(PyErr::new::<PyValueError, _>(e)),
+			Ok(tree) => WordFinder::with_blacklist(
+				str_tree::Lexicon::Tree(tree), blacklist, Arc::new(alphabet.unwrap_or_default()), layout)
+		}
+	}
+
+	/// Loads a lexicon written by `save_compiled`, without going through the word list
+	#[staticmethod]
+	#[args(blacklist = "None", alphabet = "None", tile_set = "None", layout = "None")]
+	fn from_compiled(filename: &str, blacklist: Option<String>, alphabet: Option<Alphabet>, tile_set: Option<String>, layout: Option<Layout>) -> PyResult<Self> {
+		let alphabet = choose_alphabet(alphabet, tile_set)?;
+		match str_tree::Lexicon::from_compiled(filename) {
+			Err(e) => Err(PyErr::new::<PyValueError, _>(e)),
+			Ok(dict) => WordFinder::with_blacklist(dict, blacklist, Arc::new(alphabet.unwrap_or_default()), layout)
+		}
+	}
+
+	/// Loads the DAWG of a KWG lexicon, as distributed for wolges and Macondo.
+	/// The tiles of the file are the ones of the alphabet, in the same order.
+	#[staticmethod]
+	#[args(blacklist = "None", alphabet = "None", tile_set = "None", layout = "None")]
+	fn from_kwg(filename: &str, blacklist: Option<String>, alphabet: Option<Alphabet>, tile_set: Option<String>, layout: Option<Layout>) -> PyResult<Self> {
+		let alphabet = choose_alphabet(alphabet, tile_set)?;
+		let alphabet = Arc::new(alphabet.unwrap_or_default());
+		match str_tree::Lexicon::from_kwg(filename, &alphabet.letters()) {
 			Err(e) => Err(PyErr::new::<PyValueError, _>(e)),
-			Ok(tree) => Ok(WordFinder{
-				_tree: tree, 
-				_word_buffer: str_tree::initiate_word_buf(1000)})
+			Ok(dict) => WordFinder::with_blacklist(dict, blacklist, alphabet, layout)
 		}
 	}
 
-	fn add_word(&mut self, new_word: &str) {
-		self._tree.add_word(new_word);
+	/// Loads several lexicons, given as (name, word list file) pairs, in a single structure.
+	/// Queries then take the names of the lexicons to use, all of them by default.
+	#[staticmethod]
+	#[args(blacklist = "None", alphabet = "None", tile_set = "None", layout = "None")]
+	fn from_lexicons(lexicons: Vec<(String, String)>, blacklist: Option<String>, alphabet: Option<Alphabet>, tile_set: Option<String>, layout: Option<Layout>) -> PyResult<Self> {
+		let alphabet = choose_alphabet(alphabet, tile_set)?;
+		let alphabet = Arc::new(alphabet.unwrap_or_default());
+		let mut multi = str_tree::MultiLexicon::new();
+		for (name, filename) in lexicons {
+			if let Err(e) = multi.add_lexicon(&name, &filename, &alphabet) {
+				return Err(PyErr::new::<PyValueError, _>(e));
+			}
+		}
+		return WordFinder::with_blacklist(str_tree::Lexicon::Multi(multi), blacklist, alphabet, layout);
+	}
+
+	fn alphabet(&self) -> Alphabet {
+		return (*self._alphabet).clone();
+	}
+
+	fn layout(&self) -> Layout {
+		return (*self._layout).clone();
+	}
+
+	/// Board of a message, with the alphabet and the layout of the finder
+	fn board(&self, board_msg: &str) -> PyResult<board::Board> {
+		return Ok(board::deserialize_with(board_msg, &self._alphabet, &self._layout)?);
+	}
+
+	fn lexicon_names(&self) -> Vec<String> {
+		return self._dict.names().to_vec();
+	}
+
+	/// Names of the lexicons in which the word is valid
+	fn lexicons_of(&self, word: &str) -> PyResult<Vec<String>> {
+		return Ok(self._dict.lexicons_of(&self.encode_word(word)?));
+	}
+
+	fn save_compiled(&self, filename: &str) -> PyResult<()> {
+		match self._dict.save_compiled(filename) {
+			Err(e) => Err(PyErr::new::<PyValueError, _>(e)),
+			Ok(()) => Ok(())
+		}
+	}
+
+	fn save_kwg(&self, filename: &str) -> PyResult<()> {
+		match self._dict.save_kwg(filename, &self._alphabet.letters()) {
+			Err(e) => Err(PyErr::new::<PyValueError, _>(e)),
+			Ok(()) => Ok(())
+		}
+	}
+
+	fn add_word(&mut self, new_word: &str) -> PyResult<()> {
+		let word = self.encode_word(new_word)?;
+		self._dict.add_word(&word);
+		return Ok(());
+	}
+
+	/// Returns whether the word was in the lexicon
+	fn remove_word(&mut self, word: &str) -> PyResult<bool> {
+		let word = self.encode_word(word)?;
+		return Ok(self._dict.remove_word(&word));
 	}
 
-	fn is_word(&self, word: &str) -> bool {
-		return self._tree.is_word(word);
+	/// Removes the words of the file, one per line, returns how many were in the lexicon
+	fn apply_blacklist(&mut self, filename: &str) -> PyResult<usize> {
+		match self._dict.apply_blacklist(filename, &self._alphabet) {
+			Err(e) => return Err(PyErr::new::<PyValueError, _>(e)),
+			Ok(nb_removed) => return Ok(nb_removed)
+		};
 	}
 
-	fn get_best_first_play(&mut self, word: &str, board_msg: &str) -> PyResult<Option<BestWord>> {
-		let board = board::deserialize(board_msg)?;
+	#[args(lexicons = "None")]
+	fn is_word(&self, word: &str, lexicons: Option<Vec<String>>) -> PyResult<bool> {
+		let word = self.encode_word(word)?;
+		return Ok(select_lexicon(&self._dict, &lexicons)?.is_word(&word));
+	}
+
+	/// Words matching the pattern, `?` standing for any letter and `*` for any run of letters.
+	/// With a rack, every letter of the word has to be taken from it, `?` being a blank.
+	#[args(rack = "None", lexicons = "None")]
+	fn match_pattern(&self, pattern: &str, rack: Option<String>, lexicons: Option<Vec<String>>) -> PyResult<Vec<String>> {
+		let pattern = self._alphabet.encode(pattern, |c| c == '?' || c == '*')?;
+		let rack = match rack {
+			None => None,
+			Some(rack) => Some(encode_rack(&self._alphabet, &rack)?)
+		};
+		let mut words = select_lexicon(&self._dict, &lexicons)?.match_pattern(&pattern, rack.as_deref())?;
+		self._alphabet.sort_words(&mut words);
+		return Ok(words.iter().map(|word| self._alphabet.decode(word)).collect());
+	}
+
+	/// Letters that can be put in front of the word, and the ones that can be put after it
+	#[args(lexicons = "None")]
+	fn hooks(&self, word: &str, lexicons: Option<Vec<String>>) -> PyResult<(String, String)> {
+		let word = self.encode_word(word)?;
+		let (front, back) = select_lexicon(&self._dict, &lexicons)?.hooks(&word)?;
+		let sorted = |letters: String| {
+			let mut letters: Vec<String> = letters.chars().map(String::from).collect();
+			self._alphabet.sort_words(&mut letters);
+			return self._alphabet.decode(&letters.concat());
+		};
+		return Ok((sorted(front), sorted(back)));
+	}
+
+	/// Compresses the dictionnary into a DAWG, returns the number of nodes saved.
+	/// Adding words is much slower afterwards.
+	fn compress(&mut self) -> usize {
+		return self._dict.compress();
+	}
+
+	#[args(lexicons = "None")]
+	fn get_best_first_play(&mut self, word: &str, board_msg: &str, lexicons: Option<Vec<String>>) -> PyResult<Option<BestWord>> {
+		let rack = encode_rack(&self._alphabet, word)?;
+		let board = board::deserialize_with(board_msg, &self._alphabet, &self._layout)?;
 		let bw = solver::find_best_first_word(
-			word, &board, &self._tree, Some(&mut self._word_buffer))?;
-		return Ok(bw);
+			&rack, &board, &select_lexicon(&self._dict, &lexicons)?, Some(&mut self._word_buffer))?;
+		return Ok(bw.map(|bw| decode_play(&self._alphabet, bw)));
+	}
+
+	/// Best play of a rack on a board message, or of the player on turn in a CGP position given instead of the rack.
+	/// A CGP position is played in the lexicon of its `lex` option if the finder has it.
+	#[args(board_msg = "None", lexicons = "None")]
+	fn get_best_play(&mut self, word: &str, board_msg: Option<String>, lexicons: Option<Vec<String>>) -> PyResult<Option<BestWord>> {
+		let (rack, board, lexicons, first_play) = self.play_query(word, board_msg.as_deref(), lexicons)?;
+		let dict = select_lexicon(&self._dict, &lexicons)?;
+		let bw = match first_play {
+			true => solver::find_best_first_word(&rack, &board, &dict, Some(&mut self._word_buffer))?,
+			false => solver::find_best_word::<WithoutTimer, _, _>(&rack, &board, &dict, Some(&mut self._word_buffer))?
+		};
+		return Ok(bw.map(|bw| decode_play(&self._alphabet, bw)));
+	}
+
+	#[args(lexicons = "None")]
+	fn get_top_first_plays(&mut self, word: &str, board_msg: &str, n: usize, lexicons: Option<Vec<String>>) -> PyResult<Vec<BestWord>> {
+		let rack = encode_rack(&self._alphabet, word)?;
+		let board = board::deserialize_with(board_msg, &self._alphabet, &self._layout)?;
+		let bws = solver::find_top_first_words(
+			&rack, &board, &select_lexicon(&self._dict, &lexicons)?, n, Some(&mut self._word_buffer))?;
+		return Ok(bws.into_iter().map(|bw| decode_play(&self._alphabet, bw)).collect());
+	}
+
+	/// The `n` best plays, 10 by default, of a rack on a board message or of a CGP position, as `get_best_play`
+	#[args(board_msg = "None", n = "10", lexicons = "None")]
+	fn get_top_plays(&mut self, word: &str, board_msg: Option<String>, n: usize, lexicons: Option<Vec<String>>) -> PyResult<Vec<BestWord>> {
+		let (rack, board, lexicons, first_play) = self.play_query(word, board_msg.as_deref(), lexicons)?;
+		let dict = select_lexicon(&self._dict, &lexicons)?;
+		let bws = match first_play {
+			true => solver::find_top_first_words(&rack, &board, &dict, n, Some(&mut self._word_buffer))?,
+			false => solver::find_top_words(&rack, &board, &dict, n)?
+		};
+		return Ok(bws.into_iter().map(|bw| decode_play(&self._alphabet, bw)).collect());
+	}
+
+	/// Score breakdown of a move proposed by a player, from the (row, column) of its first letter.
+	/// Raises a `MoveError` if it breaks a rule. Blanks are in uppercase in the word,
+	/// and the letters already on the board can be written `_`.
+	#[args(lexicons = "None")]
+	fn validate_move(
+		&self, 
+		word: &str, 
+		coord: (usize, usize), 
+		vertical: bool, 
+		rack: &str, 
+		board_msg: &str, 
+		lexicons: Option<Vec<String>>) 
+	-> PyResult<board::ScoreBreakdown> {
+		let word = self._alphabet.encode_with_blanks(word, |c| c == '_')?;
+		let rack = encode_rack(&self._alphabet, rack)?;
+		let board = board::deserialize_with(board_msg, &self._alphabet, &self._layout)?;
+		let breakdown = board.validate_move(&word, coord, vertical, &rack, &select_lexicon(&self._dict, &lexicons)?)?;
+		return Ok(decode_breakdown(&self._alphabet, breakdown));
+	}
+
+	/// Message of every problem of a position, see `Board::check`. `tile_set` is the name of a built-in set
+	/// whose counts of tiles are checked as well.
+	#[args(tile_set = "None", lexicons = "None")]
+	fn check_board(&self, board_msg: &str, tile_set: Option<String>, lexicons: Option<Vec<String>>) -> PyResult<Vec<String>> {
+		let tile_set = match tile_set {
+			None => None,
+			Some(name) => match TileSet::preset(&name) {
+				None => return Err(PyErr::new::<PyValueError, _>(format!("TileSet: unknown preset {}", name))),
+				tile_set => tile_set
+			}
+		};
+		let board = board::deserialize_with(board_msg, &self._alphabet, &self._layout)?;
+		let problems = board.check(&select_lexicon(&self._dict, &lexicons)?, tile_set.as_ref());
+		return Ok(problems.iter().map(|problem| problem.message()).collect());
+	}
+
+	#[args(lexicons = "None")]
+	fn iter_plays(slf: PyRef<Self>, word: &str, board_msg: &str, lexicons: Option<Vec<String>>) -> PyResult<PlayIterator> {
+		let rack = encode_rack(&slf._alphabet, word)?;
+		let board = board::deserialize_with(board_msg, &slf._alphabet, &slf._layout)?;
+		select_lexicon(&slf._dict, &lexicons)?;
+		return Ok(PlayIterator{
+			_finder: slf.into(),
+			_lexicons: lexicons,
+			_board: board,
+			_cursor: MoveCursor::new(&rack)});
+	}
+}
+
+fn select_lexicon<'a>(dict: &'a str_tree::Lexicon, lexicons: &'a Option<Vec<String>>) -> PyResult<str_tree::SelectedLexicon<'a>> {
+	match dict.select(lexicons.as_deref()) {
+		Err(name) => Err(PyErr::new::<PyValueError, _>(format!("unknown lexicon {}", name))),
+		Ok(selected) => Ok(selected)
+	}
+}
+
+// Racks use `0` or `?` for blanks
+fn encode_rack(alphabet: &Alphabet, rack: &str) -> Result<String, WordError> {
+	let rack = alphabet.encode(rack, |c| c == '0' || c == '?')?;
+	return Ok(rack.replace('?', "0"));
+}
+
+/// The alphabet given, or the one of the built-in tile set named, if any
+fn choose_alphabet(alphabet: Option<Alphabet>, tile_set: Option<String>) -> PyResult<Option<Alphabet>> {
+	match (alphabet, tile_set) {
+		(alphabet, None) => return Ok(alphabet),
+		(Some(_), Some(_)) => return Err(PyErr::new::<PyValueError, _>("give either an alphabet or a tile set")),
+		(None, Some(name)) => match TileSet::preset(&name) {
+			None => return Err(PyErr::new::<PyValueError, _>(format!("TileSet: unknown preset {}", name))),
+			Some(tile_set) => return Ok(Some(tile_set.alphabet().clone()))
+		}
+	};
+}
+
+fn decode_play(alphabet: &Alphabet, mut bw: BestWord) -> BestWord {
+	bw.word = alphabet.decode(&bw.word);
+	bw.full_word = alphabet.decode(&bw.full_word);
+	bw.leave = alphabet.decode(&bw.leave);
+	for (_, letter, _) in bw.placed.iter_mut() {
+		*letter = alphabet.decode(letter);
+	}
+	bw.breakdown = decode_breakdown(alphabet, bw.breakdown);
+	return bw;
+}
+
+fn decode_breakdown(alphabet: &Alphabet, mut breakdown: board::ScoreBreakdown) -> board::ScoreBreakdown {
+	breakdown.main_word = alphabet.decode(&breakdown.main_word);
+	for (word, _) in breakdown.cross_words.iter_mut() {
+		*word = alphabet.decode(word);
+	}
+	return breakdown;
+}
+
+impl WordFinder {
+	fn with_blacklist(dict: str_tree::Lexicon, blacklist: Option<String>, alphabet: Arc<Alphabet>, layout: Option<Layout>) -> PyResult<Self> {
+		let mut finder = WordFinder{
+			_dict: dict, 
+			_word_buffer: str_tree::initiate_word_buf(1000),
+			_alphabet: alphabet,
+			_layout: Arc::new(layout.unwrap_or_default())};
+		if let Some(filename) = blacklist {
+			finder.apply_blacklist(&filename)?;
+		}
+		return Ok(finder);
+	}
+
+	// Lexicons asked for, or else the one of the position if the finder has it
+	fn position_lexicons(&self, position: &Position, lexicons: Option<Vec<String>>) -> Option<Vec<String>> {
+		match (lexicons, position.option("lex")) {
+			(None, Some(lex)) if self._dict.names().iter().any(|name| name == lex) => return Some(vec![lex.to_string()]),
+			(lexicons, _) => return lexicons
+		};
+	}
+
+	// Rack, board and lexicons of a play query, and whether it is the first play. `word` is either a rack played
+	// on `board_msg`, or a CGP position, told apart by its space-separated fields, whose empty board calls for a first play.
+	fn play_query(&self, word: &str, board_msg: Option<&str>, lexicons: Option<Vec<String>>)
+	-> PyResult<(String, board::Board, Option<Vec<String>>, bool)> {
+		match (word.contains(char::is_whitespace), board_msg) {
+			(true, None) => {
+				let position = Position::parse(word, &self._alphabet)?;
+				let board = position.board(&self._layout)?;
+				let first_play = board.is_empty();
+				return Ok((position.rack().to_string(), board, self.position_lexicons(&position, lexicons), first_play));
+			},
+			(false, Some(board_msg)) => {
+				let rack = encode_rack(&self._alphabet, word)?;
+				return Ok((rack, board::deserialize_with(board_msg, &self._alphabet, &self._layout)?, lexicons, false));
+			},
+			(true, Some(_)) => return Err(PyErr::new::<PyValueError, _>("a CGP position comes without a board message")),
+			(false, None) => return Err(PyErr::new::<PyValueError, _>("a rack comes with a board message"))
+		};
+	}
+
+	fn encode_word(&self, word: &str) -> Result<String, WordError> {
+		return self._alphabet.encode(word, |_| false);
+	}
+}
+
+/// Same service as `WordFinder`, using a GADDAG to grow words from the anchor squares.
+/// Its best play on an empty board is the best first play.
+#[pyclass]
+struct GaddagWordFinder {
+	_gaddag: str_tree::Gaddag,
+	_alphabet: Arc<Alphabet>,
+	_layout: Arc<Layout>
+}
+
+#[pymethods]
+impl GaddagWordFinder {
+	#[new]
+	#[args(alphabet = "None", tile_set = "None", layout = "None")]
+	fn new(filename: &str, alphabet: Option<Alphabet>, tile_set: Option<String>, layout: Option<Layout>) -> PyResult<Self> {
+		let alphabet = choose_alphabet(alphabet, tile_set)?;
+		let gaddag = match &alphabet {
+			None => str_tree::Gaddag::build_dict_from_file(filename),
+			Some(alphabet) => str_tree::Gaddag::build_dict_with_alphabet(filename, alphabet)
+		};
+		let gaddag = match gaddag {
+			Err(e) => return Err(PyErr::new::<PyValueError, _>(e)),
+			Ok(gaddag) => gaddag
+		};
+		let alphabet = Arc::new(alphabet.unwrap_or_default());
+		return Ok(GaddagWordFinder{_gaddag: gaddag, _alphabet: alphabet, _layout: Arc::new(layout.unwrap_or_default())});
+	}
+
+	fn add_word(&mut self, new_word: &str) -> PyResult<()> {
+		let word = self._alphabet.encode(new_word, |_| false)?;
+		self._gaddag.add_word(&word);
+		return Ok(());
+	}
+
+	fn remove_word(&mut self, word: &str) -> PyResult<bool> {
+		let word = self._alphabet.encode(word, |_| false)?;
+		return Ok(self._gaddag.remove_word(&word));
+	}
+
+	fn is_word(&self, word: &str) -> PyResult<bool> {
+		let word = self._alphabet.encode(word, |_| false)?;
+		return Ok(self._gaddag.is_word(&word));
+	}
+
+	fn get_best_play(&self, word: &str, board_msg: &str) -> PyResult<Option<BestWord>> {
+		let rack = encode_rack(&self._alphabet, word)?;
+		let board = board::deserialize_with(board_msg, &self._alphabet, &self._layout)?;
+		let bw = solver::find_best_anchored_word(&rack, &board, &self._gaddag)?;
+		return Ok(bw.map(|bw| decode_play(&self._alphabet, bw)));
+	}
+
+	fn get_top_plays(&self, word: &str, board_msg: &str, n: usize) -> PyResult<Vec<BestWord>> {
+		let rack = encode_rack(&self._alphabet, word)?;
+		let board = board::deserialize_with(board_msg, &self._alphabet, &self._layout)?;
+		let bws = solver::find_top_anchored_words(&rack, &board, &self._gaddag, n)?;
+		return Ok(bws.into_iter().map(|bw| decode_play(&self._alphabet, bw)).collect());
+	}
+}
+
+#[pyclass]
+struct PlayIterator {
+	_finder: Py<WordFinder>,
+	_lexicons: Option<Vec<String>>,
+	_board: board::Board,
+	_cursor: MoveCursor
+}
+
+#[pymethods]
+impl PlayIterator {
+	fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+		return slf;
 	}
 
-	fn get_best_play(&mut self, word: &str, board_msg: &str) -> PyResult<Option<BestWord>> {
-		let board = board::deserialize(board_msg)?;
-		let bw = solver::find_best_word::<WithoutTimer, _, _>(
-			word, &board, &self._tree, Some(&mut self._word_buffer))?;
-		return Ok(bw);
+	fn __next__(mut slf: PyRefMut<Self>) -> PyResult<Option<BestWord>> {
+		let slf = &mut *slf;
+		return Python::with_gil(|py| {
+			let finder = slf._finder.as_ref(py).try_borrow()?;
+			let dict = select_lexicon(&finder._dict, &slf._lexicons)?;
+			let bw = slf._cursor.next_move(&slf._board, &dict)?;
+			return Ok(bw.map(|bw| decode_play(&finder._alphabet, bw)));
+		});
 	}
 }
 
@@ -98,5 +614,15 @@ impl WordFinder {
 fn rsScrabble(_py: Python, m: &PyModule) -> PyResult<()> {
 	m.add_class::<WordFinder>()?;
 	m.add_class::<BestWord>()?;
+	m.add_class::<board::ScoreBreakdown>()?;
+	m.add_class::<GaddagWordFinder>()?;
+	m.add_class::<PlayIterator>()?;
+	m.add_class::<Alphabet>()?;
+	m.add_class::<TileSet>()?;
+	m.add_class::<Layout>()?;
+	m.add_class::<board::Board>()?;
+	m.add_class::<Game>()?;
+	m.add_class::<Position>()?;
+	m.add("DeserializingError", _py.get_type::<exceptions::DeserializingError>())?;
 	return Ok(());
 }
@@ -0,0 +1,130 @@
+use crate::alphabet::{Alphabet, from_blank, is_blank, swap_case};
+use crate::board::MAX_SIDE;
+use crate::solver::Move;
+
+/// Standard notation of a move: `8H WORD` for a horizontal word starting on row 8, column H,
+/// and `H8 WORD` for a vertical one. Regular tiles are uppercase, blanks lowercase,
+/// and the tiles already on the board are between parentheses, as in `8H W(OR)D`,
+/// or written `.` when their letter isn't given, as in `8H W..D`. Those are `_` in the full word.
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum NotationError {
+	WrongCoordinate(String),
+	WrongWord(String)
+}
+
+impl Move {
+	pub fn from_notation(text: &str, alphabet: &Alphabet) -> Result<Move, NotationError> {
+		let mut parts = text.split_whitespace();
+		let (coordinate, tiles) = match (parts.next(), parts.next(), parts.next()) {
+			(Some(coordinate), Some(tiles), None) => (coordinate, tiles),
+			_ => return Err(NotationError::WrongWord(format!("notation: expected a coordinate and a word in {}", text)))
+		};
+		let (vertical, coord) = parse_coordinate(coordinate)?;
+
+		let mut mv = Move{vertical, coord, ..Default::default()};
+		let mut played_through = false;
+		for (i, segment) in tiles.split(['(', ')']).enumerate() {
+			// Odd segments are the ones between parentheses
+			if i > 0 {
+				played_through = !played_through;
+			}
+			let letters = alphabet.encode_with_blanks(&swap_case(segment), |c| c == '.' && !played_through)
+				.map_err(|_| NotationError::WrongWord(format!("notation: unknown tile in {}", tiles)))?;
+			for c in letters.chars() {
+				let idx = mv.full_word.chars().count();
+				if c == '.' {
+					mv.full_word.push('_');
+					mv.word.push('_');
+					continue;
+				}
+				mv.full_word.push(c);
+				if played_through {
+					mv.word.push('_');
+					continue;
+				}
+				mv.word.push(c);
+				let placed_coord = match vertical {
+					false => (coord.0, coord.1 + idx),
+					true => (coord.0 + idx, coord.1)
+				};
+				mv.placed.push((placed_coord, from_blank(c).to_string(), is_blank(c)));
+			}
+		}
+		if played_through || tiles.matches('(').count() != tiles.matches(')').count() {
+			return Err(NotationError::WrongWord(format!("notation: unbalanced parentheses in {}", tiles)));
+		}
+		if mv.full_word.is_empty() {
+			return Err(NotationError::WrongWord(format!("notation: no tile in {}", tiles)));
+		}
+		return Ok(mv);
+	}
+
+	/// Notation of the move, which needs its fully spelled word
+	pub fn to_notation(&self, alphabet: &Alphabet) -> Result<String, NotationError> {
+		if self.full_word.chars().count() != self.word.chars().count() {
+			return Err(NotationError::WrongWord(format!("notation: the full word of {} is unknown", self.word)));
+		}
+		let row = (self.coord.0 + 1).to_string();
+		let column = column_letter(self.coord.1)?;
+		let mut text = match self.vertical {
+			false => format!("{}{} ", row, column),
+			true => format!("{}{} ", column, row)
+		};
+
+		let mut played_through = false;
+		for (c, placed) in self.full_word.chars().zip(self.word.chars()) {
+			if c == '_' {
+				if played_through {
+					played_through = false;
+					text.push(')');
+				}
+				text.push('.');
+				continue;
+			}
+			if (placed == '_') != played_through {
+				played_through = !played_through;
+				text.push(if played_through {'('} else {')'});
+			}
+			match alphabet.display(c) {
+				None => return Err(NotationError::WrongWord(format!("notation: unknown letter {}", c))),
+				Some(display) => text.push_str(&swap_case(&display))
+			};
+		}
+		if played_through {
+			text.push(')');
+		}
+		return Ok(text);
+	}
+}
+
+fn column_letter(column: usize) -> Result<char, NotationError> {
+	if column >= MAX_SIDE {
+		return Err(NotationError::WrongCoordinate(format!("notation: column {} out of the board", column)));
+	}
+	return Ok((b'A' + column as u8) as char);
+}
+
+// Orientation and (row, column) of a coordinate such as `8H` or `H8`
+fn parse_coordinate(text: &str) -> Result<(bool, (usize, usize)), NotationError> {
+	let wrong_coordinate = || NotationError::WrongCoordinate(format!("notation: wrong coordinate {}", text));
+	let vertical = !text.starts_with(|c: char| c.is_ascii_digit());
+	let (row, column) = match vertical {
+		false => text.split_at(text.find(|c: char| !c.is_ascii_digit()).ok_or_else(wrong_coordinate)?),
+		true => {
+			let (column, row) = text.split_at(text.find(|c: char| c.is_ascii_digit()).ok_or_else(wrong_coordinate)?);
+			(row, column)
+		}
+	};
+
+	let row = row.parse::<usize>().map_err(|_| wrong_coordinate())?;
+	let mut column_chars = column.chars();
+	let column = match (column_chars.next(), column_chars.next()) {
+		(Some(c), None) if c.is_ascii_alphabetic() => (c.to_ascii_uppercase() as u8 - b'A') as usize,
+		_ => return Err(wrong_coordinate())
+	};
+	if row == 0 || row > MAX_SIDE || column >= MAX_SIDE {
+		return Err(wrong_coordinate());
+	}
+	return Ok((vertical, (row - 1, column)));
+}
@@ -1,8 +1,11 @@
 use crate::str_tree;
-use crate::str_tree::{Dictionnary, StaticWord};
+use crate::str_tree::{WordLookup, AnchoredDictionnary, StaticWord};
 
 use crate::board::BoardService;
+use crate::board::LineSquare;
 use crate::board::WordError;
+use crate::board::ScoreBreakdown;
+use crate::alphabet::{Alphabet, from_blank, is_blank};
 use crate::board::transposition::*;
 
 use crate::constraints::{PotentialWord, PotentialWordConditions, PotentialWordConditionsBuilder};
@@ -10,8 +13,14 @@ use crate::constraints::{PotentialWord, PotentialWordConditions, PotentialWordCo
 use pyo3::prelude::{pyclass, pymethods};
 
 type WordSearchResult = Result<Option<BestWord>, WordError>;
+type WordsSearchResult = Result<Vec<BestWord>, WordError>;
+
+/// A move, whether found by the solver or read from its notation
+pub type Move = BestWord;
 
 #[derive(Debug)]
+#[derive(Clone)]
+#[derive(Default)]
 #[derive(PartialEq)]
 #[pyclass]
 pub struct BestWord {
@@ -19,13 +28,85 @@ pub struct BestWord {
 	pub vertical: bool,
 	#[pyo3(get)]
 	pub coord: (usize, usize),
+	/// Word with `_` for the tiles played through, as read by `Board::apply_move`
 	#[pyo3(get)]
 	pub word: String,
 	#[pyo3(get)]
-	pub score: usize
+	pub score: usize,
+	#[pyo3(get)]
+	pub breakdown: ScoreBreakdown,
+	/// Word with the tiles played through spelled out
+	#[pyo3(get)]
+	pub full_word: String,
+	/// Tiles taken from the rack, as (coord, letter, is a blank), the letter of a blank being the one it stands for
+	#[pyo3(get)]
+	pub placed: Vec<((usize, usize), String, bool)>,
+	/// Letters left on the rack, `0` being a blank
+	#[pyo3(get)]
+	pub leave: String
+}
+
+impl BestWord {
+	// Move of the word at (x, y) of the board seen with the transposition `T`, scored in detail,
+	// its tiles being taken from the letter set
+	fn scored<T, B>(board: &B, letter_set: &str, word: &mut StaticWord, x: usize, y: usize) -> Result<BestWord, WordError>
+	where B: BoardService, T: TransposedState + TransposedBool {
+		let breakdown = board.get_score_breakdown::<T>(word.as_mut_word(), x, y)?;
+		let mut placed = Vec::new();
+		let mut leave: Vec<char> = letter_set.chars().collect();
+		for (relative_y, c) in word.as_mut_word().iter().enumerate() {
+			if *c == '_' {
+				continue;
+			}
+			placed.push((T::transposed_coord(x, y + relative_y), from_blank(*c).to_string(), is_blank(*c)));
+			let tile = match is_blank(*c) {
+				true => '0',
+				false => *c
+			};
+			if let Some(idx) = leave.iter().position(|l| *l == tile) {
+				leave.remove(idx);
+			}
+		}
+		return Ok(BestWord{
+			vertical: T::get_transposition_as_orientation(),
+			coord: T::transposed_coord(x, y),
+			word: word.str(),
+			score: breakdown.total(),
+			full_word: breakdown.main_word.clone(),
+			breakdown,
+			placed,
+			leave: leave.into_iter().collect()
+		});
+	}
 }
+
 #[pymethods]
 impl BestWord {
+	/// Move written as `8H WORD` when horizontal or `H8 WORD` when vertical, the tiles on the board being between parentheses
+	#[staticmethod]
+	#[args(alphabet = "None")]
+	#[pyo3(name = "from_notation")]
+	fn py_from_notation(text: &str, alphabet: Option<Alphabet>) -> pyo3::PyResult<Self> {
+		let alphabet = alphabet.unwrap_or_default();
+		let mut mv = Move::from_notation(text, &alphabet)?;
+		mv.word = alphabet.decode(&mv.word);
+		mv.full_word = alphabet.decode(&mv.full_word);
+		for (_, letter, _) in mv.placed.iter_mut() {
+			*letter = alphabet.decode(letter);
+		}
+		return Ok(mv);
+	}
+
+	#[args(alphabet = "None")]
+	#[pyo3(name = "to_notation")]
+	fn py_to_notation(&self, alphabet: Option<Alphabet>) -> pyo3::PyResult<String> {
+		let alphabet = alphabet.unwrap_or_default();
+		let mut mv = self.clone();
+		mv.word = alphabet.encode_with_blanks(&self.word, |c| c == '_')?;
+		mv.full_word = alphabet.encode_with_blanks(&self.full_word, |c| c == '_')?;
+		return Ok(mv.to_notation(&alphabet)?);
+	}
+
 	fn __str__(&self) -> pyo3::PyResult<String> {
 		let mut ret = "[".to_string();
 		ret.push_str(&self.word);
@@ -45,6 +126,31 @@ impl BestWord {
 	}
 }
 
+/// Keeps the `n` best words pushed so far, sorted by decreasing score.
+/// Words with the same score stay in the order they were pushed.
+pub struct TopWords {
+	n: usize,
+	words: Vec<BestWord>
+}
+impl TopWords {
+	pub fn new(n: usize) -> Self {
+		return Self{n, words: Vec::with_capacity(n)};
+	}
+
+	pub fn push(&mut self, bw: BestWord) {
+		let idx = self.words.partition_point(|w| w.score >= bw.score);
+		if idx >= self.n {
+			return;
+		}
+		self.words.insert(idx, bw);
+		self.words.truncate(self.n);
+	}
+
+	pub fn into_vec(self) -> Vec<BestWord> {
+		return self.words;
+	}
+}
+
 pub trait TransposedBool {
 	fn get_transposition_as_orientation() -> bool;
 }
@@ -55,6 +161,8 @@ impl TransposedBool for NotTransposed {
 	fn get_transposition_as_orientation() -> bool {false}
 }
 
+// The word buffer and the search parameters are passed down from the public entry points
+#[allow(clippy::too_many_arguments)]
 fn _find_best_word_at<T, B, D>(
 	letter_set: &str, 
 	x: usize, y: usize, 
@@ -64,8 +172,8 @@ fn _find_best_word_at<T, B, D>(
 	pw: &mut PotentialWord, 
 	timer: &mut Option<&mut std::time::Duration>) 
 -> WordSearchResult
-where B: BoardService, D: Dictionnary, T: TransposedState + TransposedBool {
-	let mut best_word: String = "".to_string();
+where B: BoardService, D: WordLookup, T: TransposedState + TransposedBool {
+	let mut best_word: Option<usize> = None;
 	let mut best_score = 0;
 
 	board.get_conditions::<T, _>(x, y, pw);
@@ -81,25 +189,21 @@ where B: BoardService, D: Dictionnary, T: TransposedState + TransposedBool {
 		**timer_uw += now.elapsed();
 	}
 
-	for word in words_buf {
-		let score = board.get_score::<T>(word.into_word(), x, y)?;
+	for (idx, word) in words_buf.iter_mut().enumerate() {
+		let score = board.get_score::<T>(word.as_mut_word(), x, y)?;
 		if score > best_score {
 			best_score = score;
-			best_word = word.str();
+			best_word = Some(idx);
 		}
 	}
 
-	match best_score {
-		0 => Ok(None),
-		_ => Ok(Some(BestWord{
-			vertical: T::get_transposition_as_orientation(),
-			coord: T::transposed_coord(x, y),
-			word: best_word,
-			score: best_score
-		}))
+	match best_word {
+		None => Ok(None),
+		Some(idx) => Ok(Some(BestWord::scored::<T, _>(board, letter_set, &mut words_buf[idx], x, y)?))
 	}
 }
 
+#[allow(clippy::too_many_arguments)]
 fn find_best_word_at<B, D>(
 	letter_set: &str, 
 	x: usize, y: usize, 
@@ -109,7 +213,7 @@ fn find_best_word_at<B, D>(
 	pw: &mut PotentialWord, 
 	timer: &mut Option<&mut std::time::Duration>) 
 -> WordSearchResult
-where B: BoardService, D: Dictionnary {
+where B: BoardService, D: WordLookup {
 	let bw_horizontal = _find_best_word_at::<NotTransposed, _, _>(letter_set, x, y, board, dict, words_buf, pw, timer)?;
 	let bw_vertical = _find_best_word_at::<Transposed, _, _>(letter_set, x, y, board, dict, words_buf, pw, timer)?;
 	
@@ -127,6 +231,8 @@ where B: BoardService, D: Dictionnary {
 	}
 }
 
+// Only used by the benchmark in main_opt.rs, which is not a build target
+#[allow(dead_code)]
 pub struct WithTimer;
 pub struct WithoutTimer;
 pub trait Timer {
@@ -145,9 +251,7 @@ impl Timer for WithoutTimer {
 	fn timer(_: &mut std::time::Duration)  -> Option<&mut std::time::Duration> {
 		return None;
 	}
-	fn print(_: &std::time::Duration) {
-		()
-	}
+	fn print(_: &std::time::Duration) {}
 }
 
 pub fn find_best_first_word<B, D>(
@@ -156,7 +260,7 @@ pub fn find_best_first_word<B, D>(
 	dict: &D,
 	mut words_buf_opt: Option<&mut Vec<StaticWord>>) 
 -> WordSearchResult
-where B: BoardService, D: Dictionnary {
+where B: BoardService, D: WordLookup {
 	let mut best_word:Option<BestWord> = None;
 
 	let mut small_buffer = str_tree::initiate_word_buf(1);
@@ -167,20 +271,17 @@ where B: BoardService, D: Dictionnary {
 
 	dict.get_anagrams(letter_set, words_buf, None, None, None)?;
 
+	let (start_x, start_y) = board.layout().start();
+	let side = board.layout().side();
 	let mut best_score = 0;
-	for y in 0..7 {
+	for y in 0..=start_y {
 		for word in &mut *words_buf {
-			if y + word.len() - 1 < 7 { continue; }
-			let score = board.get_score::<NotTransposed>(word.into_word(), 7, y)?;
+			if y + word.len() - 1 < start_y || y + word.len() > side { continue; }
+			let score = board.get_score::<NotTransposed>(word.as_mut_word(), start_x, y)?;
 
 			if score > best_score {
 				best_score = score;
-				best_word = Some(BestWord{
-					vertical: false,
-					coord: (7, y),
-					word: word.str(),
-					score: best_score
-				});
+				best_word = Some(BestWord::scored::<NotTransposed, _>(board, letter_set, word, start_x, y)?);
 			}
 		}
 	}
@@ -194,7 +295,7 @@ pub fn find_best_word<T: Timer, B, D>(
 	dict: &D,
 	mut words_buf_opt: Option<&mut Vec<StaticWord>>) 
 -> WordSearchResult
-where B: BoardService, D: Dictionnary {
+where B: BoardService, D: WordLookup {
 	let mut best_word:Option<BestWord> = None;
 	let mut pw = PotentialWord::new();
 
@@ -207,8 +308,9 @@ where B: BoardService, D: Dictionnary {
 	let mut base_time = std::time::Instant::now().elapsed();
  	let mut timer = T::timer(&mut base_time);
 
-	for x in 0..crate::board::SIDE {
-		for y in 0..crate::board::SIDE {
+	let side = board.layout().side();
+	for x in 0..side {
+		for y in 0..side {
 			if let Some(bw) = find_best_word_at(
 				letter_set, 
 				x, y, board, dict, 
@@ -233,3 +335,229 @@ where B: BoardService, D: Dictionnary {
 
 	return Ok(best_word)
 }
+
+/// Same search as `find_best_first_word`, but returns the `n` best words found, best first.
+pub fn find_top_first_words<B, D>(
+	letter_set: &str, 
+	board: &B, 
+	dict: &D,
+	n: usize,
+	mut words_buf_opt: Option<&mut Vec<StaticWord>>) 
+-> WordsSearchResult
+where B: BoardService, D: WordLookup {
+	let mut top_words = TopWords::new(n);
+
+	let mut small_buffer = str_tree::initiate_word_buf(1);
+	let words_buf = match words_buf_opt {
+		None => &mut small_buffer,
+		Some(ref mut wb) => wb
+	};
+
+	dict.get_anagrams(letter_set, words_buf, None, None, None)?;
+
+	let (start_x, start_y) = board.layout().start();
+	let side = board.layout().side();
+	for y in 0..=start_y {
+		for word in &mut *words_buf {
+			if y + word.len() - 1 < start_y || y + word.len() > side { continue; }
+			top_words.push(BestWord::scored::<NotTransposed, _>(board, letter_set, word, start_x, y)?);
+		}
+	}
+
+	return Ok(top_words.into_vec());
+}
+
+/// Same search as `find_best_word`, but returns the `n` best words found, best first.
+/// Words with the same score are kept in the order they were found.
+pub fn find_top_words<B, D>(
+	letter_set: &str, 
+	board: &B, 
+	dict: &D,
+	n: usize) 
+-> WordsSearchResult
+where B: BoardService, D: WordLookup {
+	let mut top_words = TopWords::new(n);
+	for bw in generate_moves(letter_set, board, dict) {
+		top_words.push(bw?);
+	}
+	return Ok(top_words.into_vec());
+}
+
+/// State of a lazy scan over every legal move of a board.
+/// It doesn't hold the board nor the dictionnary, so that the scan can be stored and
+/// resumed later on: every call to `next_move` must be done with the same board and dictionnary.
+pub struct MoveCursor {
+	letter_set: String,
+	next_position: usize,
+	x: usize,
+	y: usize,
+	vertical: bool,
+	words_buf: Vec<StaticWord>,
+	word_idx: usize,
+	pw: PotentialWord,
+	done: bool
+}
+
+impl MoveCursor {
+	pub fn new(letter_set: &str) -> Self {
+		return Self{
+			letter_set: letter_set.to_string(),
+			next_position: 0,
+			x: 0,
+			y: 0,
+			vertical: false,
+			words_buf: str_tree::initiate_word_buf(0),
+			word_idx: 0,
+			pw: PotentialWord::new(),
+			done: false
+		};
+	}
+
+	fn fill_words_buf<T, B, D>(&mut self, board: &B, dict: &D) -> Result<(), WordError>
+	where B: BoardService, D: WordLookup, T: TransposedState {
+		board.get_conditions::<T, _>(self.x, self.y, &mut self.pw);
+		return dict.get_anagrams(
+			&self.letter_set, 
+			&mut self.words_buf, 
+			self.pw.get_constraint_nb_letters(), 
+			self.pw.get_constraint_letters(), 
+			self.pw.get_constraint_words());
+	}
+
+	fn score_word<T, B>(&mut self, board: &B) -> WordSearchResult
+	where B: BoardService, T: TransposedState + TransposedBool {
+		let word = &mut self.words_buf[self.word_idx];
+		return Ok(Some(BestWord::scored::<T, _>(board, &self.letter_set, word, self.x, self.y)?));
+	}
+
+	/// Returns the next legal move, or `None` once every position of the board has been scanned.
+	/// After an error, the scan is stopped.
+	pub fn next_move<B, D>(&mut self, board: &B, dict: &D) -> WordSearchResult
+	where B: BoardService, D: WordLookup {
+		while !self.done {
+			if self.word_idx < self.words_buf.len() {
+				let ret = match self.vertical {
+					false => self.score_word::<NotTransposed, _>(board),
+					true => self.score_word::<Transposed, _>(board)
+				};
+				self.word_idx += 1;
+				self.done = ret.is_err();
+				return ret;
+			}
+
+			let side = board.layout().side();
+			if self.next_position == 2 * side * side {
+				self.done = true;
+				break;
+			}
+
+			self.x = self.next_position / (2 * side);
+			self.y = (self.next_position / 2) % side;
+			self.vertical = self.next_position % 2 == 1;
+			self.next_position += 1;
+			self.word_idx = 0;
+
+			let filled = match self.vertical {
+				false => self.fill_words_buf::<NotTransposed, _, _>(board, dict),
+				true => self.fill_words_buf::<Transposed, _, _>(board, dict)
+			};
+			if let Err(e) = filled {
+				self.done = true;
+				return Err(e);
+			}
+		}
+		return Ok(None);
+	}
+}
+
+/// Iterator over every legal move of a board, computed lazily position by position.
+pub struct MoveGenerator<'a, B, D> {
+	board: &'a B,
+	dict: &'a D,
+	cursor: MoveCursor
+}
+
+impl<'a, B, D> Iterator for MoveGenerator<'a, B, D>
+where B: BoardService, D: WordLookup {
+	type Item = Result<BestWord, WordError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		return self.cursor.next_move(self.board, self.dict).transpose();
+	}
+}
+
+pub fn generate_moves<'a, B, D>(
+	letter_set: &str, 
+	board: &'a B, 
+	dict: &'a D) 
+-> MoveGenerator<'a, B, D>
+where B: BoardService, D: WordLookup {
+	return MoveGenerator{board, dict, cursor: MoveCursor::new(letter_set)};
+}
+
+fn _find_anchored_words_at<T, B, D>(
+	letter_set: &str, 
+	x: usize, 
+	board: &B, 
+	dict: &D, 
+	line: &mut Vec<LineSquare>,
+	words_buf: &mut Vec<(usize, StaticWord)>,
+	words: &mut Vec<BestWord>) 
+-> Result<(), WordError>
+where B: BoardService, D: AnchoredDictionnary, T: TransposedState + TransposedBool {
+	board.get_line::<T>(x, line);
+	dict.get_anchored_words(letter_set, line, words_buf)?;
+
+	for (y, word) in words_buf {
+		words.push(BestWord::scored::<T, _>(board, letter_set, word, x, *y)?);
+	}
+	return Ok(());
+}
+
+/// Every legal move of the board, grown from the anchor squares of each line.
+/// On an empty board, only the horizontal moves covering the start square are returned, like `find_best_first_word`.
+pub fn find_anchored_words<B, D>(
+	letter_set: &str, 
+	board: &B, 
+	dict: &D) 
+-> WordsSearchResult
+where B: BoardService, D: AnchoredDictionnary {
+	let mut words = Vec::new();
+	let side = board.layout().side();
+	let mut line = Vec::with_capacity(side);
+	let mut words_buf = Vec::new();
+
+	for x in 0..side {
+		_find_anchored_words_at::<NotTransposed, _, _>(letter_set, x, board, dict, &mut line, &mut words_buf, &mut words)?;
+	}
+	if !board.is_empty() {
+		for x in 0..side {
+			_find_anchored_words_at::<Transposed, _, _>(letter_set, x, board, dict, &mut line, &mut words_buf, &mut words)?;
+		}
+	}
+
+	return Ok(words);
+}
+
+pub fn find_top_anchored_words<B, D>(
+	letter_set: &str, 
+	board: &B, 
+	dict: &D,
+	n: usize) 
+-> WordsSearchResult
+where B: BoardService, D: AnchoredDictionnary {
+	let mut top_words = TopWords::new(n);
+	for bw in find_anchored_words(letter_set, board, dict)? {
+		top_words.push(bw);
+	}
+	return Ok(top_words.into_vec());
+}
+
+pub fn find_best_anchored_word<B, D>(
+	letter_set: &str, 
+	board: &B, 
+	dict: &D) 
+-> WordSearchResult
+where B: BoardService, D: AnchoredDictionnary {
+	return Ok(find_top_anchored_words(letter_set, board, dict, 1)?.pop());
+}
@@ -0,0 +1,264 @@
+use crate::str_tree::MAX_SIDE;
+use crate::str_tree::{DictNode, StaticWord};
+use crate::str_tree::{ConstraintNbLetters, ConstraintLetters, ConstraintWords};
+use crate::str_tree::WordError;
+use crate::alphabet::to_blank;
+
+/// Steers an anagram search letter by letter, on top of its constraints
+pub trait Guide: Copy {
+	/// Guide once the letter `c` is added to the word, None if no word can go on with it
+	fn then(self, c: char) -> Option<Self>;
+	/// Whether the word may end with the letters added so far
+	fn may_end(self) -> bool;
+	/// Whether letters are added without being taken from the letter set
+	fn free_letters(self) -> bool;
+}
+
+// Guide of the plain anagram search, leaving everything to the constraints
+#[derive(Clone, Copy)]
+struct Unguided;
+
+impl Guide for Unguided {
+	fn then(self, _c: char) -> Option<Self> {
+		return Some(self);
+	}
+	fn may_end(self) -> bool {
+		return true;
+	}
+	fn free_letters(self) -> bool {
+		return false;
+	}
+}
+
+/// Anagram search shared by every dictionnary representation, walking the graph from `root`
+pub fn get_anagrams<N, CNbL, CL, CW>(
+	root: N, 
+	letter_set: &str, 
+	words_buf: &mut Vec<StaticWord>,
+	mut nb_letters: CNbL,
+	mut letter_constraints: CL,
+	mut word_constraints: CW) 
+ -> Result<(), WordError> where N: DictNode, CNbL: ConstraintNbLetters, CL: ConstraintLetters, CW: ConstraintWords {
+	let mut letter_set_vec:Vec<char> = letter_set.chars().collect();
+	letter_set_vec.sort_unstable();
+	nb_letters.sort_and_fuse();
+	letter_constraints.sort_and_fuse();
+	word_constraints.sort_and_fuse();
+
+	// We reformulate constraints in more memory efficient layouts
+	let mut max_nb_letters = 0;
+	let mut valid_nb_letter = [false; MAX_SIDE];
+	let mut obligatory_letters:[Option<char>; MAX_SIDE] = [None; MAX_SIDE];
+	let mut words_to_fill: [Option<(N, String)>; MAX_SIDE] = Default::default();
+	for i in 0..MAX_SIDE {
+		if nb_letters.decrease() {
+			valid_nb_letter[i] = true;
+			max_nb_letters = i;
+		}
+		obligatory_letters[i] = letter_constraints.decrease();
+		words_to_fill[i] = get_next_word_to_fill(root, word_constraints.decrease('_'))?;
+	}
+
+	let mut letter_set = StaticWord{w: Default::default(), l: 0};
+	for c in letter_set_vec.iter() {
+		letter_set.push(*c);
+	}
+
+	words_buf.clear();
+	get_anagrams_internal(
+		root,
+		Unguided,
+		0,
+		letter_set.as_mut_word(), 
+		&mut Vec::with_capacity(MAX_SIDE), 
+		max_nb_letters, 
+		&valid_nb_letter, 
+		&obligatory_letters,
+		&words_to_fill, 
+		&mut |word: &[char]| {
+			let mut static_word = StaticWord{w: Default::default(), l: 0};
+			for c in word {
+				static_word.push(*c);
+			}
+			words_buf.push(static_word);
+		});
+	Ok(())
+}
+
+/// Every word the guide leads to from `root`, its letters being taken from the letter set
+/// unless the guide adds them freely. The letters a blank `0` stands for are uppercase.
+pub fn get_guided_words<N: DictNode, G: Guide>(root: N, letter_set: &str, guide: G, words: &mut Vec<String>) {
+	let mut letter_set: Vec<char> = letter_set.chars().collect();
+	letter_set.sort_unstable();
+	let max_nb_letters = letter_set.len().min(MAX_SIDE - 1);
+	let obligatory_letters = [None; MAX_SIDE];
+	let words_to_fill: [Option<(N, String)>; MAX_SIDE] = Default::default();
+
+	get_anagrams_internal(
+		root,
+		guide,
+		0,
+		&mut letter_set,
+		&mut Vec::new(),
+		max_nb_letters,
+		&[true; MAX_SIDE],
+		&obligatory_letters,
+		&words_to_fill,
+		&mut |word: &[char]| words.push(word.iter().collect()));
+}
+
+fn get_next_word_to_fill<N: DictNode>(root: N, wtf: Option<String>) -> Result<Option<(N, String)>, WordError>
+{
+	if wtf.is_none() { return Ok(None); }
+	let binding = wtf.unwrap();
+	let segments:Vec<&str> = binding.split('_').collect();
+	let node = match root.walk(segments[0]) {
+		Some(node) => node,
+		None => return Err(WordError::UnknownConstraint(format!("Constraint word doesn't exist: {}", segments[0])))
+	};
+	Ok(Some((node, segments[1].to_string())))
+}
+
+// Every part of the search state is passed along the recursion
+#[allow(clippy::too_many_arguments)]
+fn get_anagrams_internal<N: DictNode, G: Guide, F: FnMut(&[char])>(
+	node: N, 
+	guide: G,
+	depth: usize,
+	letter_set: &mut [char],
+	current_word: &mut Vec<char>,
+	max_nb_letters: usize,
+	valid_nb_letter: &[bool; MAX_SIDE],
+	obligatory_letters: &[Option<char>; MAX_SIDE],
+	words_to_fill: &[Option<(N, String)>; MAX_SIDE],
+	emit: &mut F) {
+
+	let length = current_word.len();
+
+	// Case the current node is supposed to complete a word on the board
+	match node.letter() {
+		None => (),
+		Some(c) => {
+			let ret = match words_to_fill.get(length-1) {
+				Some(Some((ref begin_node, ref end))) => {
+					match begin_node.child(c).and_then(|child| child.walk(end)) {
+						None => true,
+						Some(end_node) => !end_node.ends_word()
+					}
+				},
+				_ => false
+			};
+			if ret {
+				return;
+			}
+		}
+	};
+
+	// Case the next letter is a constraint: continue only on that branch if it exists
+	if let Some(Some(constraint)) = obligatory_letters.get(length).copied() {
+		let (child, guide) = match (node.child(constraint), guide.then(constraint)) {
+			(Some(child), Some(guide)) => (child, guide),
+			_ => return
+		};
+		current_word.push('_');
+		get_anagrams_internal(
+			child,
+			guide,
+			depth,
+			letter_set,
+			current_word,
+			max_nb_letters,
+			valid_nb_letter,
+			obligatory_letters,
+			words_to_fill,
+			emit);
+		current_word.truncate(length);
+		return;
+	}
+
+	if node.ends_word() && valid_nb_letter[depth] && guide.may_end() { emit(current_word); }
+
+	// Case the guide adds the letters itself: follow every branch it accepts
+	if guide.free_letters() {
+		for child in node.children() {
+			let letter = child.letter().unwrap();
+			if let Some(guide) = guide.then(letter) {
+				current_word.push(letter);
+				get_anagrams_internal(
+					child,
+					guide,
+					depth,
+					letter_set,
+					current_word,
+					max_nb_letters,
+					valid_nb_letter,
+					obligatory_letters,
+					words_to_fill,
+					emit);
+				current_word.truncate(length);
+			}
+		}
+		return;
+	}
+
+	// Case there is no higher up number of letters possible: exit
+	let set_size = letter_set.len();
+	if depth >= max_nb_letters || set_size == 0 {
+		return;
+	}
+
+	// Case where there's at least one joker in set
+	if letter_set[0] == '0' {
+		for child in node.children() {
+			let letter = child.letter().unwrap();
+			if let Some(guide) = guide.then(letter) {
+				current_word.push(to_blank(letter));
+				get_anagrams_internal(
+					child,
+					guide,
+					depth + 1,
+					&mut letter_set[1..],
+					current_word,
+					max_nb_letters,
+					valid_nb_letter,
+					obligatory_letters,
+					words_to_fill,
+					emit);
+				current_word.truncate(length);
+			}
+		}
+	}
+
+	// Now take every letter in the set, and see if you can build a word from it
+	for i in 0..set_size {
+		// This avoids repetition coming from identitical letters
+		if i > 0 && letter_set[0] == letter_set[i] {
+			continue;
+		}
+
+		letter_set.swap(0, i);
+
+		match (node.child(letter_set[0]), guide.then(letter_set[0])) {
+			(Some(child), Some(guide)) => {
+				current_word.push(child.letter().unwrap());
+				get_anagrams_internal(
+					child,
+					guide,
+					depth + 1,
+					&mut letter_set[1..],
+					current_word,
+					max_nb_letters,
+					valid_nb_letter,
+					obligatory_letters,
+					words_to_fill,
+					emit);
+				current_word.truncate(length);
+			},
+			_ => continue
+		};
+	}
+
+	let temp = letter_set[0];
+	for i in 0..set_size-1 { letter_set[i] = letter_set[i+1]; }
+	letter_set[set_size-1] = temp;
+}
@@ -0,0 +1,161 @@
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+use crate::str_tree::Dawg;
+use crate::str_tree::dawg::Edge;
+
+// Layout of a compiled lexicon, every integer being little endian:
+//   magic (8 bytes) | version (u16) | alphabet size (u16) | alphabet (u32 code points)
+//   | trie node count (u32) | node count (u32) | nodes (u32 each) | checksum (u32)
+// A node packs the index of its first child in bits 0-21, the "last sibling" flag in bit 22,
+// the "is word" flag in bit 23, and its letter as an index in the alphabet (starting at 1) in bits 24-31.
+// The checksum is the 32 bits FNV-1a hash of everything before it.
+const MAGIC: &[u8; 8] = b"RSSCDAWG";
+const VERSION: u16 = 1;
+
+const CHILDREN_MASK: u32 = (1 << 22) - 1;
+const IS_LAST_BIT: u32 = 1 << 22;
+const IS_WORD_BIT: u32 = 1 << 23;
+const LETTER_SHIFT: u32 = 24;
+const MAX_ALPHABET_SIZE: usize = 255;
+
+fn checksum(bytes: &[u8]) -> u32 {
+	let mut hash: u32 = 0x811c9dc5;
+	for byte in bytes {
+		hash ^= *byte as u32;
+		hash = hash.wrapping_mul(0x01000193);
+	}
+	return hash;
+}
+
+fn invalid_data(msg: &str) -> Error {
+	return Error::new(ErrorKind::InvalidData, format!("compiled lexicon: {}", msg));
+}
+
+pub fn write_compiled<P: AsRef<Path>>(dawg: &Dawg, filename: P) -> io::Result<()> {
+	let edges = dawg.edges();
+
+	let mut alphabet: Vec<char> = edges[1..].iter().map(|edge| edge.letter).collect();
+	alphabet.sort_unstable();
+	alphabet.dedup();
+	if alphabet.len() > MAX_ALPHABET_SIZE {
+		return Err(Error::new(ErrorKind::InvalidInput, "compiled lexicon: too many different letters"));
+	}
+	if edges.len() > CHILDREN_MASK as usize {
+		return Err(Error::new(ErrorKind::InvalidInput, "compiled lexicon: too many nodes"));
+	}
+
+	let mut bytes = Vec::with_capacity(32 + 4 * (alphabet.len() + edges.len()));
+	bytes.extend_from_slice(MAGIC);
+	bytes.extend_from_slice(&VERSION.to_le_bytes());
+	bytes.extend_from_slice(&(alphabet.len() as u16).to_le_bytes());
+	for c in &alphabet {
+		bytes.extend_from_slice(&(*c as u32).to_le_bytes());
+	}
+	bytes.extend_from_slice(&(dawg.trie_nodes() as u32).to_le_bytes());
+	bytes.extend_from_slice(&(edges.len() as u32).to_le_bytes());
+	for (idx, edge) in edges.iter().enumerate() {
+		let letter = match idx {
+			0 => 0,
+			_ => alphabet.binary_search(&edge.letter).unwrap() as u32 + 1
+		};
+		let mut packed = edge.children | (letter << LETTER_SHIFT);
+		if edge.is_last { packed |= IS_LAST_BIT; }
+		if edge.is_word { packed |= IS_WORD_BIT; }
+		bytes.extend_from_slice(&packed.to_le_bytes());
+	}
+	bytes.extend_from_slice(&checksum(&bytes).to_le_bytes());
+
+	return fs::write(filename, bytes);
+}
+
+/// Reads from the start of the remaining bytes, failing if there are not enough of them
+struct Reader<'a> {
+	bytes: &'a [u8]
+}
+impl<'a> Reader<'a> {
+	fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+		if self.bytes.len() < n {
+			return Err(invalid_data("file is truncated"));
+		}
+		let (ret, rest) = self.bytes.split_at(n);
+		self.bytes = rest;
+		return Ok(ret);
+	}
+	fn u16(&mut self) -> io::Result<u16> {
+		return Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()));
+	}
+	fn u32(&mut self) -> io::Result<u32> {
+		return Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()));
+	}
+}
+
+pub fn read_compiled<P: AsRef<Path>>(filename: P) -> io::Result<Dawg> {
+	let bytes = fs::read(filename)?;
+	if bytes.len() < MAGIC.len() + 4 || &bytes[..MAGIC.len()] != MAGIC {
+		return Err(invalid_data("not a compiled lexicon"));
+	}
+	let (content, stored_checksum) = bytes.split_at(bytes.len() - 4);
+	if checksum(content) != u32::from_le_bytes(stored_checksum.try_into().unwrap()) {
+		return Err(invalid_data("checksum mismatch"));
+	}
+
+	let mut reader = Reader{bytes: &content[MAGIC.len()..]};
+	let version = reader.u16()?;
+	if version != VERSION {
+		return Err(invalid_data(&format!("unsupported version {}", version)));
+	}
+
+	let alphabet_size = reader.u16()? as usize;
+	let mut alphabet = Vec::with_capacity(alphabet_size);
+	for _ in 0..alphabet_size {
+		match char::from_u32(reader.u32()?) {
+			None => return Err(invalid_data("invalid letter in alphabet")),
+			Some(c) => alphabet.push(c)
+		};
+	}
+
+	let trie_nodes = reader.u32()? as usize;
+	let nb_edges = reader.u32()? as usize;
+	if nb_edges == 0 || reader.bytes.len() != 4 * nb_edges {
+		return Err(invalid_data("wrong node count"));
+	}
+	// Merging nodes of the trie can only make fewer of them
+	if trie_nodes < nb_edges - 1 {
+		return Err(invalid_data("fewer trie nodes than nodes"));
+	}
+
+	let mut edges = Vec::with_capacity(nb_edges);
+	let mut siblings_start = 0;
+	for idx in 0..nb_edges {
+		let packed = reader.u32()?;
+		let children = packed & CHILDREN_MASK;
+		let letter = match (packed >> LETTER_SHIFT) as usize {
+			0 => '\0',
+			idx => *alphabet.get(idx - 1).ok_or_else(|| invalid_data("unknown letter"))?
+		};
+		if children as usize >= nb_edges {
+			return Err(invalid_data("node out of bounds"));
+		}
+		// Below the root, children are written before the siblings of their parent, which rules out cycles
+		if idx > 0 && children != 0 && children as usize >= siblings_start {
+			return Err(invalid_data("children not before their parent"));
+		}
+		edges.push(Edge{
+			letter,
+			is_word: packed & IS_WORD_BIT != 0,
+			is_last: packed & IS_LAST_BIT != 0,
+			children
+		});
+		if packed & IS_LAST_BIT != 0 {
+			siblings_start = idx + 1;
+		}
+	}
+
+	if !edges.last().unwrap().is_last {
+		return Err(invalid_data("unterminated node list"));
+	}
+
+	return Ok(Dawg::from_edges(edges, trie_nodes));
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use crate::str_tree::{Dictionnary, WordLookup, DictNode, StaticWord, StrTree};
+use crate::str_tree::{ConstraintNbLetters, ConstraintLetters, ConstraintWords};
+use crate::str_tree::{get_anagrams, match_pattern};
+use crate::str_tree::WordError;
+
+/// Arc of the graph: its letter, whether the path ending with it spells a word,
+/// whether it is the last of its siblings, and the index of the first arc leaving it (0 if none).
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, Hash)]
+pub(super) struct Edge {
+	pub(super) letter: char,
+	pub(super) is_word: bool,
+	pub(super) is_last: bool,
+	pub(super) children: u32
+}
+
+/// Minimized form of a `StrTree`, where identical suffixes are shared, stored in one flat array.
+/// The arcs leaving a node are contiguous, and the first arc is a sentinel pointing to the root's arcs.
+pub struct Dawg {
+	edges: Vec<Edge>,
+	trie_nodes: usize
+}
+
+impl std::fmt::Debug for Dawg {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&format!("DAWG - {} nodes ({} saved)", self.nb_nodes(), self.saved_nodes()))
+	}
+}
+
+impl WordLookup for Dawg {
+	fn get_anagrams<CNbL, CL, CW>(
+		&self,
+		letter_set: &str,
+		words_buf: &mut Vec<StaticWord>,
+		nb_letters: CNbL,
+		letter_constraints: CL,
+		word_constraints: CW)
+	 -> Result<(), WordError> where CNbL: ConstraintNbLetters, CL: ConstraintLetters, CW: ConstraintWords {
+		return get_anagrams(self.root(), letter_set, words_buf, nb_letters, letter_constraints, word_constraints);
+	}
+
+	fn is_word(&self, word: &str) -> bool {
+		match self.root().walk(word) {
+			None => return false,
+			Some(node) => return node.ends_word()
+		};
+	}
+
+	fn match_pattern(&self, pattern: &str, rack: Option<&str>) -> Result<Vec<String>, WordError> {
+		return match_pattern(self.root(), pattern, rack);
+	}
+}
+
+impl Dictionnary for Dawg {
+	fn build_dict_from_file(filename: &str) -> std::io::Result<Dawg> {
+		return Ok(Dawg::compile(&StrTree::build_dict_from_file(filename)?));
+	}
+
+	// The graph is rebuilt from scratch, this is much slower than on a `StrTree`
+	fn add_word(&mut self, word: &str) {
+		let mut tree = self.to_str_tree();
+		tree.add_word(word);
+		*self = Dawg::compile(&tree);
+	}
+
+	// Same as `add_word`, the graph is rebuilt
+	fn remove_word(&mut self, word: &str) -> bool {
+		if !self.is_word(word) {
+			return false;
+		}
+		let mut tree = self.to_str_tree();
+		tree.remove_word(word);
+		*self = Dawg::compile(&tree);
+		return true;
+	}
+
+	// The graph is only rebuilt once for the whole list
+	fn remove_words(&mut self, words: &[String]) -> usize {
+		let mut tree = self.to_str_tree();
+		let nb_removed = tree.remove_words(words);
+		if nb_removed > 0 {
+			*self = Dawg::compile(&tree);
+		}
+		return nb_removed;
+	}
+}
+
+impl Dawg {
+	pub fn compile(tree: &StrTree) -> Dawg {
+		let mut edges = vec![Edge{letter: '\0', is_word: false, is_last: true, children: 0}];
+		let mut known_siblings = HashMap::new();
+		let mut trie_nodes = 0;
+		edges[0].children = Dawg::compile_children(tree, &mut edges, &mut known_siblings, &mut trie_nodes);
+		return Dawg{edges, trie_nodes};
+	}
+
+	// Children are compiled first, so that identical suffixes end up with identical arcs
+	fn compile_children(
+		node: &StrTree,
+		edges: &mut Vec<Edge>,
+		known_siblings: &mut HashMap<Vec<Edge>, u32>,
+		trie_nodes: &mut usize)
+	-> u32 {
+		let mut children: Vec<&StrTree> = node.children().collect();
+		if children.is_empty() {
+			return 0;
+		}
+		children.sort_unstable_by_key(|child| child.letter());
+		*trie_nodes += children.len();
+
+		let mut siblings: Vec<Edge> = children.iter().map(|child| Edge{
+			letter: child.letter().unwrap(),
+			is_word: child.ends_word(),
+			is_last: false,
+			children: Dawg::compile_children(child, edges, known_siblings, trie_nodes)
+		}).collect();
+		siblings.last_mut().unwrap().is_last = true;
+
+		if let Some(idx) = known_siblings.get(&siblings) {
+			return *idx;
+		}
+		let idx = edges.len() as u32;
+		edges.extend(siblings.iter());
+		known_siblings.insert(siblings, idx);
+		return idx;
+	}
+
+	pub(super) fn from_edges(edges: Vec<Edge>, trie_nodes: usize) -> Dawg {
+		return Dawg{edges, trie_nodes};
+	}
+
+	// Same as `from_edges`, when the size of the equivalent `StrTree` is not known
+	pub(super) fn from_edges_counting(edges: Vec<Edge>) -> Dawg {
+		let mut known_counts = HashMap::new();
+		let trie_nodes = Dawg::count_trie_nodes(&edges, edges[0].children, &mut known_counts);
+		return Dawg{edges, trie_nodes};
+	}
+
+	// Number of trie nodes below the sibling list starting at `idx`
+	fn count_trie_nodes(edges: &[Edge], idx: u32, known_counts: &mut HashMap<u32, usize>) -> usize {
+		if idx == 0 {
+			return 0;
+		}
+		if let Some(count) = known_counts.get(&idx) {
+			return *count;
+		}
+
+		let mut count = 0;
+		let mut i = idx as usize;
+		loop {
+			count += 1 + Dawg::count_trie_nodes(edges, edges[i].children, known_counts);
+			if edges[i].is_last {
+				break;
+			}
+			i += 1;
+		}
+		known_counts.insert(idx, count);
+		return count;
+	}
+
+	pub(super) fn edges(&self) -> &[Edge] {
+		return &self.edges;
+	}
+
+	pub(super) fn trie_nodes(&self) -> usize {
+		return self.trie_nodes;
+	}
+
+	pub fn to_str_tree(&self) -> StrTree {
+		let mut tree = StrTree::init();
+		for word in self.root().words() {
+			tree.add_word(&word);
+		}
+		return tree;
+	}
+
+	fn root(&self) -> DawgNode<'_> {
+		return DawgNode{dawg: self, idx: 0};
+	}
+
+	/// Number of nodes of the graph, the root excluded
+	pub fn nb_nodes(&self) -> usize {
+		return self.edges.len() - 1;
+	}
+
+	/// Number of nodes saved compared with the equivalent `StrTree`
+	pub fn saved_nodes(&self) -> usize {
+		return self.trie_nodes - self.nb_nodes();
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct DawgNode<'a> {
+	dawg: &'a Dawg,
+	idx: u32
+}
+
+pub struct DawgChildren<'a> {
+	dawg: &'a Dawg,
+	next: u32
+}
+
+impl<'a> Iterator for DawgChildren<'a> {
+	type Item = DawgNode<'a>;
+
+	fn next(&mut self) -> Option<DawgNode<'a>> {
+		if self.next == 0 {
+			return None;
+		}
+		let ret = DawgNode{dawg: self.dawg, idx: self.next};
+		self.next = match self.dawg.edges[self.next as usize].is_last {
+			true => 0,
+			false => self.next + 1
+		};
+		return Some(ret);
+	}
+}
+
+impl<'a> DictNode for DawgNode<'a> {
+	fn letter(self) -> Option<char> {
+		match self.idx {
+			0 => None,
+			_ => Some(self.dawg.edges[self.idx as usize].letter)
+		}
+	}
+
+	fn ends_word(self) -> bool {
+		return self.dawg.edges[self.idx as usize].is_word;
+	}
+
+	fn child(self, c: char) -> Option<Self> {
+		return self.children().find(|child| child.letter() == Some(c));
+	}
+
+	fn children(self) -> impl Iterator<Item = Self> {
+		return DawgChildren{dawg: self.dawg, next: self.dawg.edges[self.idx as usize].children};
+	}
+}
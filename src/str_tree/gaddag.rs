@@ -0,0 +1,232 @@
+use crate::str_tree::MAX_SIDE;
+use crate::str_tree::{read_words, Alphabet};
+use crate::str_tree::{AnchoredDictionnary, Dictionnary, WordLookup, DictNode, StaticWord, StrTree};
+use crate::str_tree::{LineSquare, LineSquare::*};
+use crate::str_tree::WordError;
+use crate::alphabet::to_blank;
+
+/// Marks the end of the reversed prefix in a GADDAG path
+const SEPARATOR: char = '+';
+
+/// Every word `w` is stored once per split point `i`, as the path `rev(w[..i]) + SEPARATOR + w[i..]`.
+/// Starting from any letter of a word, one can then grow it leftwards, then rightwards.
+pub struct Gaddag {
+	tree: StrTree
+}
+
+impl std::fmt::Debug for Gaddag {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("GADDAG - ")?;
+		self.tree.fmt(f)
+	}
+}
+
+impl AnchoredDictionnary for Gaddag {
+	fn build_dict_from_file(filename: &str) -> std::io::Result<Gaddag> {
+		return Ok(Gaddag::from_words(&read_words(filename, None, false)?));
+	}
+
+	fn get_anchored_words(
+		&self,
+		letter_set: &str,
+		line: &[LineSquare],
+		words_buf: &mut Vec<(usize, StaticWord)>)
+	-> Result<(), WordError> {
+		let mut letter_set_vec:Vec<char> = letter_set.chars().collect();
+		letter_set_vec.sort_unstable();
+
+		words_buf.clear();
+		let mut search = AnchoredSearch{
+			line,
+			cross_checks: self.cross_checks(line),
+			anchor: 0,
+			current_word: ['_'; MAX_SIDE],
+			words: words_buf
+		};
+		for (anchor, square) in line.iter().enumerate() {
+			if let Empty{anchor: true, ..} = square {
+				search.anchor = anchor;
+				search.go_left(anchor, &self.tree, &mut letter_set_vec);
+			}
+		}
+		Ok(())
+	}
+
+	fn add_word(&mut self, word: &str) {
+		for path in Gaddag::paths(word) {
+			self.tree.add_word(&path);
+		}
+	}
+
+	fn remove_word(&mut self, word: &str) -> bool {
+		let mut removed = false;
+		for path in Gaddag::paths(word) {
+			removed |= self.tree.remove_word(&path);
+		}
+		return removed;
+	}
+
+	fn is_word(&self, word: &str) -> bool {
+		let mut chars = word.chars();
+		let mut path = match chars.next() {
+			None => return false,
+			Some(c) => c.to_string()
+		};
+		path.push(SEPARATOR);
+		path.extend(chars);
+		return self.tree.is_word(&path);
+	}
+}
+
+impl Gaddag {
+	/// GADDAG of a word list written with the tiles of the alphabet
+	pub fn build_dict_with_alphabet(filename: &str, alphabet: &Alphabet) -> std::io::Result<Gaddag> {
+		return Ok(Gaddag::from_words(&read_words(filename, Some(alphabet), false)?));
+	}
+
+	fn from_words(words: &[String]) -> Gaddag {
+		let mut ret = Gaddag{tree: StrTree::init()};
+		for word in words {
+			ret.add_word(word);
+		}
+		return ret;
+	}
+
+	// Letters making a word with the cross word of each empty square of the line, None for the squares without one
+	fn cross_checks(&self, line: &[LineSquare]) -> Vec<Option<Vec<char>>> {
+		return line.iter().map(|square| match square {
+			Empty{cross_word: Some(word), ..} => Some((&self.tree).children()
+				.filter_map(|child| child.letter())
+				.filter(|c| *c != SEPARATOR && self.is_word(&word.complete(*c)))
+				.collect()),
+			_ => None
+		}).collect();
+	}
+
+	fn paths(word: &str) -> Vec<String> {
+		let chars: Vec<char> = word.chars().collect();
+		let mut paths = Vec::with_capacity(chars.len());
+		for i in 1..chars.len()+1 {
+			let mut path: String = chars[..i].iter().rev().collect();
+			path.push(SEPARATOR);
+			path.extend(chars[i..].iter());
+			paths.push(path);
+		}
+		return paths;
+	}
+}
+
+/// State of the search for every word of a line going through one anchor.
+/// `current_word` is indexed like the line, and only the squares covered so far are meaningful.
+struct AnchoredSearch<'a, 'b> {
+	line: &'a [LineSquare],
+	cross_checks: Vec<Option<Vec<char>>>,
+	anchor: usize,
+	current_word: [char; MAX_SIDE],
+	words: &'b mut Vec<(usize, StaticWord)>
+}
+
+impl<'a, 'b> AnchoredSearch<'a, 'b> {
+	fn fits_cross_word(&self, pos: usize, c: char) -> bool {
+		match &self.cross_checks[pos] {
+			None => true,
+			Some(letters) => letters.contains(&c)
+		}
+	}
+
+	// Calls `next` for every way of covering the empty square `pos` with a letter of the set
+	fn place_letter<F>(&mut self, pos: usize, node: &'a StrTree, letter_set: &mut Vec<char>, mut next: F)
+	where F: FnMut(&mut Self, &'a StrTree, &mut Vec<char>) {
+		if let Letter(_) = self.line[pos] {
+			return;
+		}
+
+		for i in 0..letter_set.len() {
+			// This avoids repetition coming from identitical letters
+			if i > 0 && letter_set[i-1] == letter_set[i] {
+				continue;
+			}
+
+			let c = letter_set.remove(i);
+			if c == '0' {
+				for child in node.children() {
+					let letter = child.letter().unwrap();
+					if letter == SEPARATOR || !self.fits_cross_word(pos, letter) {
+						continue;
+					}
+					self.current_word[pos] = to_blank(letter);
+					next(self, child, letter_set);
+				}
+			} else if let Some(child) = node.child(c) {
+				if self.fits_cross_word(pos, c) {
+					self.current_word[pos] = c;
+					next(self, child, letter_set);
+				}
+			}
+			letter_set.insert(i, c);
+		}
+	}
+
+	// Covers the square `pos`, at the left of the anchor or on it, going through the reversed prefix
+	fn go_left(&mut self, pos: usize, node: &'a StrTree, letter_set: &mut Vec<char>) {
+		match self.line[pos] {
+			Letter(c) => {
+				if let Some(child) = node.child(c) {
+					self.current_word[pos] = '_';
+					self.after_left(pos, child, letter_set);
+				}
+			},
+			// Squares left of the anchor that are anchors themselves are handled by their own search
+			Empty{anchor, ..} => {
+				if anchor && pos != self.anchor {
+					return;
+				}
+				self.place_letter(pos, node, letter_set, |search, child, set| search.after_left(pos, child, set));
+			}
+		}
+	}
+
+	// The word currently covers the squares from `pos` to the anchor
+	fn after_left(&mut self, pos: usize, node: &'a StrTree, letter_set: &mut Vec<char>) {
+		if pos > 0 {
+			self.go_left(pos-1, node, letter_set);
+			if let Letter(_) = self.line[pos-1] {
+				return;
+			}
+		}
+
+		if let Some(child) = node.child(SEPARATOR) {
+			self.go_right(pos, self.anchor+1, child, letter_set);
+		}
+	}
+
+	// The word currently covers the squares from `start` to `pos`, excluded
+	fn go_right(&mut self, start: usize, pos: usize, node: &'a StrTree, letter_set: &mut Vec<char>) {
+		let next_letter = match self.line.get(pos) {
+			Some(Letter(c)) => Some(*c),
+			_ => None
+		};
+
+		if next_letter.is_none() && node.ends_word() {
+			let mut word = StaticWord{w: Default::default(), l: 0};
+			for c in &self.current_word[start..pos] {
+				word.push(*c);
+			}
+			self.words.push((start, word));
+		}
+
+		if pos == self.line.len() {
+			return;
+		}
+
+		match next_letter {
+			Some(c) => {
+				if let Some(child) = node.child(c) {
+					self.current_word[pos] = '_';
+					self.go_right(start, pos+1, child, letter_set);
+				}
+			},
+			None => self.place_letter(pos, node, letter_set, |search, child, set| search.go_right(start, pos+1, child, set))
+		}
+	}
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+use crate::str_tree::Dawg;
+use crate::str_tree::dawg::Edge;
+
+// A KWG (Kurnia Word Graph, as used by wolges and Macondo) is an array of little endian u32 nodes.
+// A node packs the index of its first child in bits 0-21, the "last sibling" flag in bit 22,
+// the "accepts" flag in bit 23 and its tile in bits 24-31 (0 is the GADDAG separator, letters start at 1).
+// Node 0 points to the root of the DAWG, and node 1 to the root of the GADDAG, which we ignore.
+const ARC_INDEX_MASK: u32 = (1 << 22) - 1;
+const IS_END_BIT: u32 = 1 << 22;
+const ACCEPTS_BIT: u32 = 1 << 23;
+const TILE_SHIFT: u32 = 24;
+
+fn invalid_data(msg: &str) -> Error {
+	return Error::new(ErrorKind::InvalidData, format!("kwg: {}", msg));
+}
+
+/// Reads the DAWG part of a KWG file, `letters[i]` being the letter of tile `i+1`
+pub fn read_kwg<P: AsRef<Path>>(filename: P, letters: &[char]) -> io::Result<Dawg> {
+	let bytes = fs::read(filename)?;
+	if bytes.len() % 4 != 0 || bytes.len() < 8 {
+		return Err(invalid_data("file size is not a whole number of nodes"));
+	}
+	let nodes: Vec<u32> = bytes.chunks_exact(4).map(|b| u32::from_le_bytes(b.try_into().unwrap())).collect();
+
+	// Only the sibling lists reachable from the DAWG root are copied, keeping their relative order
+	let mut edges = vec![Edge{letter: '\0', is_word: false, is_last: true, children: 0}];
+	let mut new_indices: HashMap<u32, u32> = HashMap::new();
+	let mut to_copy = vec![nodes[0] & ARC_INDEX_MASK];
+	while let Some(old_idx) = to_copy.pop() {
+		if old_idx == 0 || new_indices.contains_key(&old_idx) {
+			continue;
+		}
+		new_indices.insert(old_idx, 0);
+
+		let mut idx = old_idx as usize;
+		loop {
+			let node = *nodes.get(idx).ok_or_else(|| invalid_data("node out of bounds"))?;
+			to_copy.push(node & ARC_INDEX_MASK);
+			if node & IS_END_BIT != 0 {
+				break;
+			}
+			idx += 1;
+		}
+	}
+
+	let mut old_indices: Vec<u32> = new_indices.keys().copied().collect();
+	old_indices.sort_unstable();
+	for old_idx in old_indices {
+		new_indices.insert(old_idx, edges.len() as u32);
+		let mut idx = old_idx as usize;
+		loop {
+			let node = nodes[idx];
+			let letter = match (node >> TILE_SHIFT) as usize {
+				0 => return Err(invalid_data("separator in the DAWG")),
+				tile => *letters.get(tile - 1).ok_or_else(|| invalid_data("unknown tile"))?
+			};
+			edges.push(Edge{
+				letter,
+				is_word: node & ACCEPTS_BIT != 0,
+				is_last: node & IS_END_BIT != 0,
+				children: node & ARC_INDEX_MASK
+			});
+			if node & IS_END_BIT != 0 {
+				break;
+			}
+			idx += 1;
+		}
+	}
+
+	edges[0].children = nodes[0] & ARC_INDEX_MASK;
+	for edge in edges.iter_mut() {
+		if edge.children != 0 {
+			edge.children = new_indices[&edge.children];
+		}
+	}
+
+	return Ok(Dawg::from_edges_counting(edges));
+}
+
+/// Writes a KWG file with only a DAWG part, `letters[i]` being the letter of tile `i+1`
+pub fn write_kwg<P: AsRef<Path>>(dawg: &Dawg, filename: P, letters: &[char]) -> io::Result<()> {
+	let edges = dawg.edges();
+	if edges.len() + 1 > ARC_INDEX_MASK as usize {
+		return Err(Error::new(ErrorKind::InvalidInput, "kwg: too many nodes"));
+	}
+
+	// Our edges are shifted by one, to make room for the GADDAG root in node 1
+	let shifted = |idx: u32| if idx == 0 { 0 } else { idx + 1 };
+	let mut nodes = vec![
+		shifted(edges[0].children) | IS_END_BIT,
+		IS_END_BIT];
+	for edge in &edges[1..] {
+		let tile = match letters.iter().position(|c| *c == edge.letter) {
+			Some(i) if i < 255 => i as u32 + 1,
+			_ => return Err(Error::new(ErrorKind::InvalidInput, format!("kwg: no tile for letter {}", edge.letter)))
+		};
+		let mut node = shifted(edge.children) | (tile << TILE_SHIFT);
+		if edge.is_last { node |= IS_END_BIT; }
+		if edge.is_word { node |= ACCEPTS_BIT; }
+		nodes.push(node);
+	}
+
+	let bytes: Vec<u8> = nodes.iter().flat_map(|node| node.to_le_bytes()).collect();
+	return fs::write(filename, bytes);
+}
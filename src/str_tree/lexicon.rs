@@ -0,0 +1,194 @@
+use crate::str_tree::{Dictionnary, WordLookup, StaticWord, StrTree, Dawg};
+use crate::str_tree::{MultiLexicon, LexiconSelection};
+use crate::str_tree::{read_compiled, write_compiled};
+use crate::str_tree::{read_kwg, write_kwg};
+use crate::str_tree::{ConstraintNbLetters, ConstraintLetters, ConstraintWords};
+use crate::str_tree::WordError;
+
+/// Any of the dictionnary representations, to be able to switch between them at runtime
+#[derive(Debug)]
+pub enum Lexicon {
+	Tree(StrTree),
+	Dawg(Dawg),
+	Multi(MultiLexicon)
+}
+
+impl WordLookup for Lexicon {
+	fn get_anagrams<CNbL, CL, CW>(
+		&self,
+		letter_set: &str,
+		words_buf: &mut Vec<StaticWord>,
+		nb_letters: CNbL,
+		letter_constraints: CL,
+		word_constraints: CW)
+	 -> Result<(), WordError> where CNbL: ConstraintNbLetters, CL: ConstraintLetters, CW: ConstraintWords {
+		match self {
+			Lexicon::Tree(tree) => tree.get_anagrams(letter_set, words_buf, nb_letters, letter_constraints, word_constraints),
+			Lexicon::Dawg(dawg) => dawg.get_anagrams(letter_set, words_buf, nb_letters, letter_constraints, word_constraints),
+			Lexicon::Multi(multi) => multi.get_anagrams(letter_set, words_buf, nb_letters, letter_constraints, word_constraints)
+		}
+	}
+
+	fn is_word(&self, word: &str) -> bool {
+		match self {
+			Lexicon::Tree(tree) => tree.is_word(word),
+			Lexicon::Dawg(dawg) => dawg.is_word(word),
+			Lexicon::Multi(multi) => multi.is_word(word)
+		}
+	}
+
+	fn match_pattern(&self, pattern: &str, rack: Option<&str>) -> Result<Vec<String>, WordError> {
+		match self {
+			Lexicon::Tree(tree) => tree.match_pattern(pattern, rack),
+			Lexicon::Dawg(dawg) => dawg.match_pattern(pattern, rack),
+			Lexicon::Multi(multi) => multi.match_pattern(pattern, rack)
+		}
+	}
+}
+
+impl Dictionnary for Lexicon {
+	fn build_dict_from_file(filename: &str) -> std::io::Result<Lexicon> {
+		return Ok(Lexicon::Tree(StrTree::build_dict_from_file(filename)?));
+	}
+
+	fn add_word(&mut self, word: &str) {
+		match self {
+			Lexicon::Tree(tree) => tree.add_word(word),
+			Lexicon::Dawg(dawg) => dawg.add_word(word),
+			Lexicon::Multi(multi) => multi.add_word(word)
+		}
+	}
+
+	fn remove_word(&mut self, word: &str) -> bool {
+		match self {
+			Lexicon::Tree(tree) => tree.remove_word(word),
+			Lexicon::Dawg(dawg) => dawg.remove_word(word),
+			Lexicon::Multi(multi) => multi.remove_word(word)
+		}
+	}
+
+	fn remove_words(&mut self, words: &[String]) -> usize {
+		match self {
+			Lexicon::Tree(tree) => tree.remove_words(words),
+			Lexicon::Dawg(dawg) => dawg.remove_words(words),
+			Lexicon::Multi(multi) => multi.remove_words(words)
+		}
+	}
+}
+
+impl Lexicon {
+	/// Turns the lexicon into a DAWG, and returns the number of nodes saved.
+	/// Several lexicons are kept as they are, as a DAWG only knows one of them.
+	pub fn compress(&mut self) -> usize {
+		if let Lexicon::Tree(tree) = self {
+			*self = Lexicon::Dawg(Dawg::compile(tree));
+		}
+		match self {
+			Lexicon::Dawg(dawg) => dawg.saved_nodes(),
+			_ => 0
+		}
+	}
+
+	pub fn from_compiled(filename: &str) -> std::io::Result<Lexicon> {
+		return Ok(Lexicon::Dawg(read_compiled(filename)?));
+	}
+
+	/// `letters[i]` is the letter of the tile `i+1` of the file
+	pub fn from_kwg(filename: &str, letters: &[char]) -> std::io::Result<Lexicon> {
+		return Ok(Lexicon::Dawg(read_kwg(filename, letters)?));
+	}
+
+	pub fn save_compiled(&self, filename: &str) -> std::io::Result<()> {
+		match self {
+			Lexicon::Tree(tree) => write_compiled(&Dawg::compile(tree), filename),
+			Lexicon::Dawg(dawg) => write_compiled(dawg, filename),
+			Lexicon::Multi(_) => Err(several_lexicons_error())
+		}
+	}
+
+	pub fn save_kwg(&self, filename: &str, letters: &[char]) -> std::io::Result<()> {
+		match self {
+			Lexicon::Tree(tree) => write_kwg(&Dawg::compile(tree), filename, letters),
+			Lexicon::Dawg(dawg) => write_kwg(dawg, filename, letters),
+			Lexicon::Multi(_) => Err(several_lexicons_error())
+		}
+	}
+
+	/// Names of the lexicons, empty if there is only an unnamed one
+	pub fn names(&self) -> &[String] {
+		match self {
+			Lexicon::Multi(multi) => multi.names(),
+			_ => &[]
+		}
+	}
+
+	/// Names of the lexicons containing the word
+	pub fn lexicons_of(&self, word: &str) -> Vec<String> {
+		let mask = match self {
+			Lexicon::Multi(multi) => multi.lexicons_of(word),
+			_ => 0
+		};
+		return self.names().iter().enumerate()
+			.filter(|(i, _)| mask & (1 << i) != 0)
+			.map(|(_, name)| name.clone())
+			.collect();
+	}
+
+	/// Restricts the lexicon to the named lexicons, `None` selecting all of them.
+	/// The error is the first unknown name.
+	pub fn select(&self, names: Option<&[String]>) -> Result<SelectedLexicon<'_>, String> {
+		let names = match names {
+			None => return Ok(SelectedLexicon::Whole(self)),
+			Some(names) => names
+		};
+		let multi = match self {
+			Lexicon::Multi(multi) => multi,
+			_ => return Err(names.first().cloned().unwrap_or_default())
+		};
+		let mut mask = 0;
+		for name in names {
+			mask |= multi.mask_of(name).ok_or_else(|| name.clone())?;
+		}
+		return Ok(SelectedLexicon::Part(multi.select(mask)));
+	}
+}
+
+fn several_lexicons_error() -> std::io::Error {
+	return std::io::Error::new(std::io::ErrorKind::InvalidInput, "several lexicons can not be saved as one");
+}
+
+/// Either a whole `Lexicon`, or some of its lexicons
+pub enum SelectedLexicon<'a> {
+	Whole(&'a Lexicon),
+	Part(LexiconSelection<'a>)
+}
+
+impl<'a> WordLookup for SelectedLexicon<'a> {
+	fn get_anagrams<CNbL, CL, CW>(
+		&self,
+		letter_set: &str,
+		words_buf: &mut Vec<StaticWord>,
+		nb_letters: CNbL,
+		letter_constraints: CL,
+		word_constraints: CW)
+	 -> Result<(), WordError> where CNbL: ConstraintNbLetters, CL: ConstraintLetters, CW: ConstraintWords {
+		match self {
+			SelectedLexicon::Whole(lexicon) => lexicon.get_anagrams(letter_set, words_buf, nb_letters, letter_constraints, word_constraints),
+			SelectedLexicon::Part(selection) => selection.get_anagrams(letter_set, words_buf, nb_letters, letter_constraints, word_constraints)
+		}
+	}
+
+	fn is_word(&self, word: &str) -> bool {
+		match self {
+			SelectedLexicon::Whole(lexicon) => lexicon.is_word(word),
+			SelectedLexicon::Part(selection) => selection.is_word(word)
+		}
+	}
+
+	fn match_pattern(&self, pattern: &str, rack: Option<&str>) -> Result<Vec<String>, WordError> {
+		match self {
+			SelectedLexicon::Whole(lexicon) => lexicon.match_pattern(pattern, rack),
+			SelectedLexicon::Part(selection) => selection.match_pattern(pattern, rack)
+		}
+	}
+}
@@ -1,19 +1,44 @@
 mod read_file;
-pub use read_file::cnt_lines;
-pub use read_file::read_lines;
+pub use read_file::read_words;
 
 mod tree_building;
 pub use tree_building::StrTree;
 
-pub use crate::board::SIDE;
+mod gaddag;
+pub use gaddag::Gaddag;
+
+mod dawg;
+pub use dawg::Dawg;
+
+mod compiled_file;
+pub use compiled_file::{read_compiled, write_compiled};
+
+mod kwg_file;
+pub use kwg_file::{read_kwg, write_kwg};
+
+mod multi_lexicon;
+pub use multi_lexicon::{MultiLexicon, LexiconSelection};
+
+mod lexicon;
+pub use lexicon::{Lexicon, SelectedLexicon};
+
+mod anagrams;
+use anagrams::get_anagrams;
+
+mod pattern;
+use pattern::{match_pattern, hooks};
+
+pub use crate::alphabet::Alphabet;
+pub use crate::board::MAX_SIDE;
 pub use crate::board::WordError;
 pub use crate::constraints::{ConstraintNbLetters, ConstraintLetters, ConstraintWords};
+pub use crate::constraints::LineSquare;
 
 #[derive(Copy)]
 #[derive(Clone)]
 #[derive(Debug)]
 pub struct StaticWord{
-	w: [char; SIDE],
+	w: [char; MAX_SIDE],
 	l: usize
 }
 impl StaticWord {
@@ -24,7 +49,7 @@ impl StaticWord {
 		self.w[self.l] = c;
 		self.l += 1;
 	}
-	pub fn into_word(&mut self) -> &mut [char] {
+	pub fn as_mut_word(&mut self) -> &mut [char] {
 		&mut self.w[0..self.l]
 	}
 	pub fn len(&self) -> usize {
@@ -32,13 +57,46 @@ impl StaticWord {
 	}
 }
 
+/// A node of a dictionnary graph: the root has no letter, and every path from it spells a word prefix.
+/// Nodes are light handles, so that the anagram search can walk any representation of the graph.
+pub trait DictNode: Copy {
+	fn letter(self) -> Option<char>;
+	fn ends_word(self) -> bool;
+	fn child(self, c: char) -> Option<Self>;
+	fn children(self) -> impl Iterator<Item = Self>;
+
+	fn walk(self, word: &str) -> Option<Self> {
+		let mut node = self;
+		for c in word.chars() {
+			node = node.child(c)?;
+		}
+		return Some(node);
+	}
+
+	/// Every word spelled by a path starting from this node
+	fn words(self) -> Vec<String> {
+		let mut words = Vec::new();
+		let mut stack = vec![(self, String::new())];
+		while let Some((node, prefix)) = stack.pop() {
+			if node.ends_word() {
+				words.push(prefix.clone());
+			}
+			for child in node.children() {
+				let mut word = prefix.clone();
+				word.push(child.letter().unwrap());
+				stack.push((child, word));
+			}
+		}
+		return words;
+	}
+}
+
 pub fn initiate_word_buf(n: usize) -> Vec<StaticWord> {
 	Vec::with_capacity(n)
 }
 
-pub trait Dictionnary {
-	fn build_dict_from_file(filename: &str) -> std::io::Result<Self> where Self: Sized;
-	
+/// Read-only part of a dictionnary, which is all the solver needs
+pub trait WordLookup {
 	fn get_anagrams<CNbL, CL, CW>(
 		&self, 
 		letter_set: &str, 
@@ -48,10 +106,73 @@ pub trait Dictionnary {
 		word_constraint: CW)
 	-> Result<(), WordError> where CNbL: ConstraintNbLetters, CL: ConstraintLetters, CW: ConstraintWords;
 
+	fn is_word(&self, word: &str) -> bool;
+
+	/// Words matching a pattern where `?` is any letter and `*` any run of letters,
+	/// the whole word being optionally taken from a rack
+	fn match_pattern(&self, pattern: &str, rack: Option<&str>) -> Result<Vec<String>, WordError>;
+
+	/// Letters that can be put in front of the word, then after it, to make another word
+	fn hooks(&self, word: &str) -> Result<(String, String), WordError> where Self: Sized {
+		return hooks(self, word);
+	}
+}
+
+pub trait Dictionnary: WordLookup {
+	fn build_dict_from_file(filename: &str) -> std::io::Result<Self> where Self: Sized;
+
+	fn add_word(&mut self, new_word: &str);
+	/// Returns whether the word was in the dictionnary
+	fn remove_word(&mut self, word: &str) -> bool;
+
+	/// Returns the number of words that were actually in the dictionnary
+	fn remove_words(&mut self, words: &[String]) -> usize {
+		let mut nb_removed = 0;
+		for word in words {
+			if self.remove_word(word) {
+				nb_removed += 1;
+			}
+		}
+		return nb_removed;
+	}
+
+	/// Removes the words of a file, one per line, ignoring blank lines and lines starting with `#`.
+	/// Returns the number of words that were actually in the dictionnary.
+	fn apply_blacklist(&mut self, filename: &str, alphabet: &Alphabet) -> std::io::Result<usize> {
+		return Ok(self.remove_words(&read_words(filename, Some(alphabet), true)?));
+	}
+}
+
+/// Dictionnary growing words in both directions from the anchor squares of a board line,
+/// instead of trying every starting square like `WordLookup::get_anagrams`.
+pub trait AnchoredDictionnary {
+	fn build_dict_from_file(filename: &str) -> std::io::Result<Self> where Self: Sized;
+
+	/// Fills `words_buf` with every word of the line covering at least one anchor, along with
+	/// the index of its first square. Letters already on the line are written as `_`.
+	fn get_anchored_words(
+		&self,
+		letter_set: &str,
+		line: &[LineSquare],
+		words_buf: &mut Vec<(usize, StaticWord)>)
+	-> Result<(), WordError>;
+
 	fn add_word(&mut self, new_word: &str);
+	/// Returns whether the word was in the dictionnary
+	fn remove_word(&mut self, word: &str) -> bool;
 	fn is_word(&self, word: &str) -> bool;
 }
 
+/// Dictionnary of a word list written with the latin letters
 pub fn build_dict_from_file(filename: &str) -> std::io::Result<StrTree> {
 	return StrTree::build_dict_from_file(filename);
 }
+
+pub fn build_dict_with_alphabet(filename: &str, alphabet: &Alphabet) -> std::io::Result<StrTree> {
+	let words = read_words(filename, Some(alphabet), false)?;
+	let mut tree = StrTree::init();
+	for word in &words {
+		tree.add_word(word);
+	}
+	return Ok(tree);
+}
@@ -0,0 +1,254 @@
+use std::io::{self, Error, ErrorKind};
+
+use crate::str_tree::{read_words, Alphabet};
+use crate::str_tree::{Dictionnary, WordLookup, DictNode, StaticWord};
+use crate::str_tree::{get_anagrams, match_pattern};
+use crate::str_tree::{ConstraintNbLetters, ConstraintLetters, ConstraintWords};
+use crate::str_tree::WordError;
+
+/// Set of lexicons, bit `i` standing for the `i`-th lexicon added
+pub type LexiconMask = u32;
+pub const MAX_LEXICONS: usize = LexiconMask::BITS as usize;
+
+/// Same as a `StrTree`, with the lexicons a node is a word of,
+/// and the lexicons having a word going through it, to prune the search.
+struct LexiconTree {
+	data: Option<char>,
+	words: LexiconMask,
+	below: LexiconMask,
+	children: Vec<LexiconTree>
+}
+
+impl LexiconTree {
+	fn init(data: Option<char>) -> Self {
+		return Self{
+			data,
+			words: 0,
+			below: 0,
+			children: Vec::new()};
+	}
+
+	fn get_child_idx(&self, c: char) -> Option<usize> {
+		return self.children.iter().position(|child| child.data == Some(c));
+	}
+
+	fn get_node(&self, word: &str) -> Option<&LexiconTree> {
+		let mut node = self;
+		for c in word.chars() {
+			node = &node.children[node.get_child_idx(c)?];
+		}
+		return Some(node);
+	}
+
+	fn add_word(&mut self, word: &str, mask: LexiconMask) {
+		let mut node = self;
+		node.below |= mask;
+		for c in word.chars() {
+			let idx = match node.get_child_idx(c) {
+				Some(idx) => idx,
+				None => {
+					node.children.push(LexiconTree::init(Some(c)));
+					node.children.len() - 1
+				}
+			};
+			node = &mut node.children[idx];
+			node.below |= mask;
+		}
+		node.words |= mask;
+	}
+
+	// Same pruning as `StrTree::remove_suffix`, once the word is in none of the lexicons
+	fn remove_suffix(&mut self, suffix: &[char], mask: LexiconMask) -> bool {
+		let removed = match suffix.split_first() {
+			None => {
+				let was_word = self.words & mask != 0;
+				self.words &= !mask;
+				was_word
+			},
+			Some((c, rest)) => {
+				let idx = match self.get_child_idx(*c) {
+					None => return false,
+					Some(idx) => idx
+				};
+				let removed = self.children[idx].remove_suffix(rest, mask);
+				if self.children[idx].below == 0 {
+					self.children.swap_remove(idx);
+				}
+				removed
+			}
+		};
+		self.below = self.children.iter().fold(self.words, |below, child| below | child.below);
+		return removed;
+	}
+}
+
+/// Several lexicons sharing one tree, each word knowing which lexicons it belongs to
+pub struct MultiLexicon {
+	names: Vec<String>,
+	tree: LexiconTree
+}
+
+impl std::fmt::Debug for MultiLexicon {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&format!("Lexicons {}", self.names.join(", ")))
+	}
+}
+
+impl WordLookup for MultiLexicon {
+	fn get_anagrams<CNbL, CL, CW>(
+		&self,
+		letter_set: &str,
+		words_buf: &mut Vec<StaticWord>,
+		nb_letters: CNbL,
+		letter_constraints: CL,
+		word_constraints: CW)
+	 -> Result<(), WordError> where CNbL: ConstraintNbLetters, CL: ConstraintLetters, CW: ConstraintWords {
+		return self.select(self.all()).get_anagrams(letter_set, words_buf, nb_letters, letter_constraints, word_constraints);
+	}
+
+	/// Whether the word is in any of the lexicons
+	fn is_word(&self, word: &str) -> bool {
+		return self.lexicons_of(word) != 0;
+	}
+
+	fn match_pattern(&self, pattern: &str, rack: Option<&str>) -> Result<Vec<String>, WordError> {
+		return self.select(self.all()).match_pattern(pattern, rack);
+	}
+}
+
+/// Adding or removing a word applies to every lexicon
+impl Dictionnary for MultiLexicon {
+	fn build_dict_from_file(filename: &str) -> io::Result<MultiLexicon> {
+		let mut ret = MultiLexicon::new();
+		ret.add_lexicon_words(filename, &read_words(filename, None, false)?)?;
+		return Ok(ret);
+	}
+
+	fn add_word(&mut self, word: &str) {
+		self.add_word_to(word, self.all());
+	}
+
+	fn remove_word(&mut self, word: &str) -> bool {
+		return self.remove_word_from(word, self.all());
+	}
+}
+
+impl MultiLexicon {
+	pub fn new() -> Self {
+		return Self{names: Vec::new(), tree: LexiconTree::init(None)};
+	}
+
+	/// Adds a lexicon from a word list written with the tiles of the alphabet, and returns its mask
+	pub fn add_lexicon(&mut self, name: &str, filename: &str, alphabet: &Alphabet) -> io::Result<LexiconMask> {
+		let words = read_words(filename, Some(alphabet), false)?;
+		return self.add_lexicon_words(name, &words);
+	}
+
+	fn add_lexicon_words(&mut self, name: &str, words: &[String]) -> io::Result<LexiconMask> {
+		if self.names.len() == MAX_LEXICONS {
+			return Err(Error::new(ErrorKind::InvalidInput, format!("lexicons: no more than {} lexicons", MAX_LEXICONS)));
+		}
+		if self.mask_of(name).is_some() {
+			return Err(Error::new(ErrorKind::InvalidInput, format!("lexicons: {} is already loaded", name)));
+		}
+
+		let mask = 1 << self.names.len();
+		for word in words {
+			self.tree.add_word(word, mask);
+		}
+		self.names.push(name.to_string());
+		return Ok(mask);
+	}
+
+	pub fn names(&self) -> &[String] {
+		return &self.names;
+	}
+
+	pub fn mask_of(&self, name: &str) -> Option<LexiconMask> {
+		let idx = self.names.iter().position(|n| n == name)?;
+		return Some(1 << idx);
+	}
+
+	/// Mask of every lexicon
+	pub fn all(&self) -> LexiconMask {
+		return ((1u64 << self.names.len()) - 1) as LexiconMask;
+	}
+
+	/// Mask of the lexicons containing the word
+	pub fn lexicons_of(&self, word: &str) -> LexiconMask {
+		match self.tree.get_node(word) {
+			None => return 0,
+			Some(node) => return node.words
+		};
+	}
+
+	pub fn add_word_to(&mut self, word: &str, mask: LexiconMask) {
+		self.tree.add_word(word, mask & self.all());
+	}
+
+	/// Returns whether the word was in any of the lexicons of the mask
+	pub fn remove_word_from(&mut self, word: &str, mask: LexiconMask) -> bool {
+		let chars: Vec<char> = word.chars().collect();
+		return self.tree.remove_suffix(&chars, mask);
+	}
+
+	/// Dictionnary restricted to the lexicons of the mask, a word being valid if it is in any of them
+	pub fn select(&self, mask: LexiconMask) -> LexiconSelection<'_> {
+		return LexiconSelection{lexicons: self, mask};
+	}
+}
+
+pub struct LexiconSelection<'a> {
+	lexicons: &'a MultiLexicon,
+	mask: LexiconMask
+}
+
+impl<'a> WordLookup for LexiconSelection<'a> {
+	fn get_anagrams<CNbL, CL, CW>(
+		&self,
+		letter_set: &str,
+		words_buf: &mut Vec<StaticWord>,
+		nb_letters: CNbL,
+		letter_constraints: CL,
+		word_constraints: CW)
+	 -> Result<(), WordError> where CNbL: ConstraintNbLetters, CL: ConstraintLetters, CW: ConstraintWords {
+		let root = SelectedNode{node: &self.lexicons.tree, mask: self.mask};
+		return get_anagrams(root, letter_set, words_buf, nb_letters, letter_constraints, word_constraints);
+	}
+
+	fn is_word(&self, word: &str) -> bool {
+		return self.lexicons.lexicons_of(word) & self.mask != 0;
+	}
+
+	fn match_pattern(&self, pattern: &str, rack: Option<&str>) -> Result<Vec<String>, WordError> {
+		return match_pattern(SelectedNode{node: &self.lexicons.tree, mask: self.mask}, pattern, rack);
+	}
+}
+
+/// Node of a `LexiconTree`, seen through the lexicons of the mask only
+#[derive(Clone, Copy)]
+pub struct SelectedNode<'a> {
+	node: &'a LexiconTree,
+	mask: LexiconMask
+}
+
+impl<'a> DictNode for SelectedNode<'a> {
+	fn letter(self) -> Option<char> {
+		return self.node.data;
+	}
+
+	fn ends_word(self) -> bool {
+		return self.node.words & self.mask != 0;
+	}
+
+	fn child(self, c: char) -> Option<Self> {
+		return self.children().find(|child| child.letter() == Some(c));
+	}
+
+	fn children(self) -> impl Iterator<Item = Self> {
+		let mask = self.mask;
+		return self.node.children.iter()
+			.filter(move |child| child.below & mask != 0)
+			.map(move |child| SelectedNode{node: child, mask});
+	}
+}
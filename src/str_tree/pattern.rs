@@ -0,0 +1,120 @@
+use crate::str_tree::{WordLookup, DictNode};
+use crate::str_tree::WordError;
+use crate::str_tree::anagrams::{Guide, get_guided_words};
+
+/// Wildcard standing for any single letter
+pub const ANY_LETTER: char = '?';
+/// Wildcard standing for any run of letters, possibly empty
+pub const ANY_RUN: char = '*';
+
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+enum PatternItem {
+	Letter(char),
+	AnyLetter,
+	AnyRun
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<PatternItem>, WordError> {
+	let mut items = Vec::new();
+	for c in pattern.chars() {
+		let item = match c {
+			ANY_LETTER => PatternItem::AnyLetter,
+			ANY_RUN => PatternItem::AnyRun,
+			c if c.is_alphabetic() => PatternItem::Letter(c.to_lowercase().next().unwrap()),
+			c => return Err(WordError::UnknownChar(format!("match_pattern: unexpected char {}", c)))
+		};
+		// Consecutive runs match the same words as a single one
+		if item == PatternItem::AnyRun && items.last() == Some(&PatternItem::AnyRun) {
+			continue;
+		}
+		items.push(item);
+	}
+	return Ok(items);
+}
+
+// Each item of a pattern is a state of the search, items.len() being the end of the pattern
+const MAX_PATTERN_ITEMS: usize = 127;
+
+// Guide through a pattern, holding the items a word may go on with as a bit set
+#[derive(Clone, Copy)]
+struct PatternGuide<'a> {
+	items: &'a [PatternItem],
+	states: u128,
+	free: bool
+}
+
+impl<'a> PatternGuide<'a> {
+	fn new(items: &'a [PatternItem], free: bool) -> Self {
+		return PatternGuide{items, states: Self::skip_runs(items, 1), free};
+	}
+
+	// A run may be empty, so the item after it may come as well
+	fn skip_runs(items: &[PatternItem], mut states: u128) -> u128 {
+		for (i, item) in items.iter().enumerate() {
+			if *item == PatternItem::AnyRun && states & (1 << i) != 0 {
+				states |= 1 << (i + 1);
+			}
+		}
+		return states;
+	}
+}
+
+impl Guide for PatternGuide<'_> {
+	fn then(self, c: char) -> Option<Self> {
+		let mut states = 0;
+		for (i, item) in self.items.iter().enumerate() {
+			if self.states & (1 << i) == 0 {
+				continue;
+			}
+			match item {
+				PatternItem::Letter(letter) if *letter == c => states |= 1 << (i + 1),
+				PatternItem::Letter(_) => (),
+				PatternItem::AnyLetter => states |= 1 << (i + 1),
+				PatternItem::AnyRun => states |= 1 << i
+			};
+		}
+		if states == 0 {
+			return None;
+		}
+		return Some(PatternGuide{states: Self::skip_runs(self.items, states), ..self});
+	}
+	fn may_end(self) -> bool {
+		return self.states & (1 << self.items.len()) != 0;
+	}
+	fn free_letters(self) -> bool {
+		return self.free;
+	}
+}
+
+/// Every word matching the pattern, from the dictionnary graph at `root`. Without a rack, the pattern
+/// alone makes the word. With one, every letter of the word is taken from it, `?` or `0` being a blank:
+/// the letters it stands for are then uppercase. Patterns have at most 127 letters and wildcards.
+pub fn match_pattern<N: DictNode>(root: N, pattern: &str, rack: Option<&str>) -> Result<Vec<String>, WordError> {
+	let items = parse_pattern(pattern)?;
+	if items.len() > MAX_PATTERN_ITEMS {
+		return Err(WordError::UnknownConstraint(format!("match_pattern: more than {} letters and wildcards", MAX_PATTERN_ITEMS)));
+	}
+	let letter_set: String = rack.unwrap_or("").chars()
+		.map(|c| if c == ANY_LETTER { '0' } else { c.to_lowercase().next().unwrap() })
+		.collect();
+	let mut words = Vec::new();
+	get_guided_words(root, &letter_set, PatternGuide::new(&items, rack.is_none()), &mut words);
+	return Ok(words);
+}
+
+/// Letters that can be put in front of the word, then after it, to make another word
+pub fn hooks<D: WordLookup>(dict: &D, word: &str) -> Result<(String, String), WordError> {
+	if let Some(c) = word.chars().find(|c| !c.is_alphabetic()) {
+		return Err(WordError::UnknownChar(format!("hooks: unexpected char {}", c)));
+	}
+	let mut front = String::new();
+	for hooked in dict.match_pattern(&format!("{}{}", ANY_LETTER, word), None)? {
+		front.push(hooked.chars().next().unwrap());
+	}
+	let mut back = String::new();
+	for hooked in dict.match_pattern(&format!("{}{}", word, ANY_LETTER), None)? {
+		back.push(hooked.chars().last().unwrap());
+	}
+	return Ok((front, back));
+}
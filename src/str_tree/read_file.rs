@@ -2,6 +2,8 @@ use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 
+use crate::alphabet::Alphabet;
+
 // The output is wrapped in a Result to allow matching on errors
 // Returns an Iterator to the Reader of the lines of the file.
 pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
@@ -10,13 +12,23 @@ where P: AsRef<Path>, {
     return Ok(io::BufReader::new(file).lines());
 }
 
-pub fn cnt_lines<P>(filename: P) -> io::Result<u32>
+/// Words of a word list, one per line, skipping blank lines, and lines starting with `#` if `comments` is set.
+/// They are written with the tiles of the alphabet, a line it cannot read being an InvalidData error,
+/// or taken as they are without an alphabet.
+pub fn read_words<P>(filename: P, alphabet: Option<&Alphabet>, comments: bool) -> io::Result<Vec<String>>
 where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-
-    let mut cnt:u32 = 0;
-    for _ in io::BufReader::new(file).lines() {
-        cnt = cnt + 1;
+    let mut words = Vec::new();
+    for (line_nb, line) in read_lines(filename)?.enumerate() {
+        let line = line?;
+        let word = line.trim();
+        if word.is_empty() || (comments && word.starts_with('#')) {
+            continue;
+        }
+        match alphabet.map(|alphabet| alphabet.encode(word, |_| false)) {
+            None => words.push(word.to_string()),
+            Some(Err(e)) => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {:?}", line_nb + 1, e))),
+            Some(Ok(encoded)) => words.push(encoded)
+        };
     }
-    return Ok(cnt);
-}
\ No newline at end of file
+    return Ok(words);
+}
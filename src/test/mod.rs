@@ -1,10 +1,14 @@
 use crate::str_tree;
-use crate::str_tree::{Dictionnary, StaticWord};
+use crate::str_tree::{Dictionnary, WordLookup, StaticWord};
 
-fn to_string_vec(words: &Vec<StaticWord>) -> Vec<String> {
+fn to_string_vec(words: &[StaticWord]) -> Vec<String> {
 	words.iter().map(|w| w.str()).collect()
 }
 
+fn to_string_vec_from(words: &[&str]) -> Vec<String> {
+	return words.iter().map(|word| word.to_string()).collect();
+}
+
 fn get_anagrams(letters: &str) -> Vec<String> {
 	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
 	let mut vec = str_tree::initiate_word_buf(0);
@@ -12,9 +16,9 @@ fn get_anagrams(letters: &str) -> Vec<String> {
 	to_string_vec(&vec)
 }
 
-fn found_in_vec<T>(el: &T, vec: &Vec<T>) -> bool 
+fn found_in_vec<T>(el: &T, vec: &[T]) -> bool 
 where T: std::cmp::PartialEq {
-	for el2 in vec.into_iter() {
+	for el2 in vec.iter() {
 		if *el == *el2 {
 			return true;
 		}
@@ -22,14 +26,14 @@ where T: std::cmp::PartialEq {
 	return false;
 }
 
-fn unordered_equal<T>(v1: &Vec<T>, v2: &Vec<T>) -> bool 
+fn unordered_equal<T>(v1: &[T], v2: &[T]) -> bool 
 where T: std::cmp::PartialEq {
-	for el in v1.into_iter() {
+	for el in v1.iter() {
 		if !found_in_vec(el, v2) {
 			return false;
 		}
 	}
-	for el in v2.into_iter() {
+	for el in v2.iter() {
 		if !found_in_vec(el, v1) {
 			return false;
 		}
@@ -132,7 +136,7 @@ fn nb_letters_constraints() {
 	tree.get_anagrams("arbre", &mut words, Some(vec![3]), None, None).unwrap();
 	assert!(unordered_equal(
 		&to_string_vec(&words), 
-		&vec!["bar".to_string()]));
+		&["bar".to_string()]));
 
 	tree.get_anagrams("arbre", &mut words, Some(vec![5]), None, None).unwrap();
 	assert!(unordered_equal(
@@ -192,22 +196,22 @@ fn letters_constraints() {
 	tree.get_anagrams("rbre", &mut words, None, Some(vec![(0, 'a')]), None).unwrap();
 	assert!(unordered_equal(
 		&to_string_vec(&words), 
-		&vec!["_rbre".to_string()]));
+		&["_rbre".to_string()]));
 	tree.get_anagrams("arbe", &mut words, None, Some(vec![(1, 'r')]), None).unwrap();
 	assert!(unordered_equal(
 		&to_string_vec(&words), 
-		&vec!["a_bre".to_string()]));
+		&["a_bre".to_string()]));
 
 	tree.get_anagrams("arbe", &mut words, None, Some(vec![(3, 'r')]), None).unwrap();
 	println!("{:?}", words);
 	assert!(unordered_equal(
 		&to_string_vec(&words), 
-		&vec!["arb_e".to_string(), "bar_e".to_string()]));
+		&["arb_e".to_string(), "bar_e".to_string()]));
 
 	tree.get_anagrams("arbr", &mut words, None, Some(vec![(4, 'e')]), None).unwrap();
 	assert!(unordered_equal(
 		&to_string_vec(&words), 
-		&vec!["arbr_".to_string(), "barr_".to_string(), "bar".to_string()]));
+		&["arbr_".to_string(), "barr_".to_string(), "bar".to_string()]));
 }
 
 #[test]
@@ -316,16 +320,35 @@ fn board_serialization() {
 
 	let b = board::deserialize(&str_board).expect_err("Unlikely Success");
 	match b {
-		DeserializingError::WrongLength(_) => (),
+		DeserializingError::WrongLength{expected: 225, found: 224} => (),
 		_ => panic!("Wrong error type")
 	}
 
 	str_board.push('!');
 	let b2 = board::deserialize(&str_board).expect_err("Unlikely success");
 	match b2 {
-		DeserializingError::UnknownSymbol(_) => (),
+		DeserializingError::UnknownSymbol{ref symbol, row: 15, column: 15} if symbol == "!" => (),
 		_ => panic!("Wrong error type")
 	}
+	assert_eq!(b2.message(), "deserialize: unknown symbol ! at row 15, column 15");
+
+	// Every problem is reported
+	let str_board = format!("{}%{}[zz]", "_".repeat(20), "_".repeat(200));
+	let error = board::deserialize(&str_board).unwrap_err();
+	assert_eq!(error.problems(), vec![
+		&DeserializingError::UnknownSymbol{symbol: "%".to_string(), row: 2, column: 6},
+		&DeserializingError::UnknownSymbol{symbol: "[zz]".to_string(), row: 15, column: 12},
+		&DeserializingError::WrongLength{expected: 225, found: 222}]);
+	assert_eq!(error.message(),
+		"deserialize: unknown symbol % at row 2, column 6; deserialize: unknown symbol [zz] at row 15, column 12; deserialize: 222 squares instead of 225");
+	assert_eq!(board::deserialize(&format!("{}[ab", "_".repeat(224))).unwrap_err(),
+		DeserializingError::UnknownSymbol{symbol: "[ab".to_string(), row: 15, column: 15});
+
+	// Rows and columns are the ones of the grid
+	let mut grid = board::deserialize(&"_".repeat(225)).unwrap().pretty::<NotTransposed>();
+	grid = grid.replacen("_", "?", 20);
+	assert_eq!(board::deserialize(&grid).unwrap_err().problems()[16],
+		&DeserializingError::UnknownSymbol{symbol: "?".to_string(), row: 2, column: 2});
 }
 
 #[test]
@@ -484,6 +507,7 @@ fn get_score() {
 }
 
 use crate::board::WordError;
+use crate::board::ScoreBreakdown;
 
 #[test]
 fn get_score_errors() {
@@ -551,7 +575,45 @@ fn complete_test() {
 	assert_eq!(bw, Ok(None));
 
 	bw = solver::find_best_word::<WithoutTimer, _, _>("arbre", &board, &tree, None);
-	assert_eq!(bw, Ok(Some(solver::BestWord{coord: (11, 3), word: "arbr_".to_string(), vertical: false, score: 12})));
+	let breakdown = ScoreBreakdown{
+		main_word: "arbre".to_string(),
+		base_value: 7,
+		letter_multipliers: vec![1, 1, 1, 1, 1],
+		word_multiplier: 2,
+		main_score: 14,
+		cross_words: vec![],
+		bingo_bonus: 0};
+	let placed = vec![
+		((11, 3), "a".to_string(), false),
+		((11, 4), "r".to_string(), false),
+		((11, 5), "b".to_string(), false),
+		((11, 6), "r".to_string(), false)];
+	assert_eq!(bw, Ok(Some(solver::BestWord{
+		coord: (11, 3), 
+		word: "arbr_".to_string(), 
+		vertical: false, 
+		score: 14, 
+		breakdown, 
+		full_word: "arbre".to_string(), 
+		placed, 
+		leave: "e".to_string()})));
+}
+
+#[test]
+fn played_through_tiles_score() {
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(Layout::preset("scrabble").unwrap());
+	let mut str_board = "_".repeat(96);
+	str_board.push_str("k________");
+	str_board.push_str("_______z_______");
+	str_board.push_str(&"_".repeat(105));
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+
+	// The z already on the board counts its face value in the main word, but not its double word square,
+	// and the k counts in the cross word "ka" as well
+	assert_eq!(board.get_score::<NotTransposed>(&['m','a','_','o','u','t'], 7, 5), Ok(16 + 11));
+	// Both the main word and the cross word count the tiles already on the board
+	assert_eq!(board.get_score::<Transposed>(&['z','_'], 7, 6), Ok(20 + 20));
 }
 
 #[test]
@@ -582,3 +644,1309 @@ fn complete_test_error() {
 		_ => panic!("Wrong error type")
 	}
 }
+
+// Board message of the position most solver tests play on
+fn reference_board_message() -> String {
+	let mut str_board = "".to_string();
+	str_board.push_str("6__2___6___2__6");
+	str_board.push_str("_5___3___3___5_");
+	str_board.push_str("__5___2_2___5__");
+	str_board.push_str("2__5___2___5__2");
+	str_board.push_str("____5_____5____");
+	str_board.push_str("_3___3___3___3_");
+	str_board.push_str("__2___2_2___2__");
+	str_board.push_str("6__2___a___2__6");
+	str_board.push_str("__2___2r2___2__");
+	str_board.push_str("_3___3_be3___3_");
+	str_board.push_str("____5__R__5____");
+	str_board.push_str("2__5___e___5__2");
+	str_board.push_str("__5___2_2___5__");
+	str_board.push_str("_5___3___3___5_");
+	str_board.push_str("6__2___6___2__6");
+	return str_board;
+}
+
+#[test]
+fn top_words() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+
+	let board = board::deserialize(&reference_board_message()).expect("Error when deserializing board message");
+
+	assert_eq!(solver::find_top_words("arbre", &board, &tree, 0), Ok(vec![]));
+	assert_eq!(solver::find_top_words("", &board, &tree, 5), Ok(vec![]));
+
+	let all = solver::find_top_words("arbre", &board, &tree, 1000).unwrap();
+	assert!(all.len() > 3);
+	for i in 1..all.len() {
+		assert!(all[i-1].score >= all[i].score);
+	}
+	assert_eq!(all[0].score, 14);
+
+	let top3 = solver::find_top_words("arbre", &board, &tree, 3).unwrap();
+	assert_eq!(top3, all[0..3].to_vec());
+}
+
+#[test]
+fn top_words_ties_are_stable() {
+	let word = |w: &str, score: usize| solver::BestWord{coord: (0, 0), word: w.to_string(), vertical: false, score, ..Default::default()};
+
+	let mut top = solver::TopWords::new(3);
+	top.push(word("a", 2));
+	top.push(word("b", 5));
+	top.push(word("c", 2));
+	top.push(word("d", 5));
+	top.push(word("e", 1));
+	assert_eq!(top.into_vec(), vec![word("b", 5), word("d", 5), word("a", 2)]);
+}
+
+#[test]
+fn top_first_words() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let board = board::deserialize(&"_".repeat(225)).expect("Error when deserializing board message");
+
+	let best = solver::find_best_first_word("arbre", &board, &tree, None).unwrap().unwrap();
+	let top = solver::find_top_first_words("arbre", &board, &tree, 2, None).unwrap();
+	assert_eq!(top.len(), 2);
+	assert_eq!(top[0].score, best.score);
+	assert!(top[0].score >= top[1].score);
+}
+
+#[test]
+fn generate_moves() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+
+	let board = board::deserialize(&reference_board_message()).expect("Error when deserializing board message");
+
+	assert_eq!(solver::generate_moves("", &board, &tree).count(), 0);
+
+	let moves: Vec<solver::BestWord> = solver::generate_moves("arbre", &board, &tree)
+		.collect::<Result<_, _>>()
+		.unwrap();
+	let best = solver::find_best_word::<WithoutTimer, _, _>("arbre", &board, &tree, None).unwrap().unwrap();
+	assert!(found_in_vec(&best, &moves));
+	assert_eq!(moves.iter().map(|bw| bw.score).max(), Some(best.score));
+	assert_eq!(moves.len(), solver::find_top_words("arbre", &board, &tree, 1000).unwrap().len());
+
+	let horizontal: Vec<solver::BestWord> = solver::generate_moves("arbre", &board, &tree)
+		.map(|bw| bw.unwrap())
+		.filter(|bw| !bw.vertical)
+		.take(2)
+		.collect();
+	assert_eq!(horizontal.len(), 2);
+}
+
+#[test]
+fn generate_moves_error() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+
+	// The reference position, with a z in place of its last e
+	let str_board = reference_board_message().replacen("2__5___e___5__2", "2__5___z___5__2", 1);
+	let board = board::deserialize(&str_board).expect("Error when deserializing board message");
+
+	let mut moves = solver::generate_moves("arbre", &board, &tree).skip_while(|bw| bw.is_ok());
+	match moves.next() {
+		Some(Err(WordError::UnknownConstraint(_))) => (),
+		_ => panic!("Wrong error type")
+	}
+	assert!(moves.next().is_none());
+}
+
+use crate::str_tree::AnchoredDictionnary;
+
+fn sorted_moves(mut moves: Vec<solver::BestWord>) -> Vec<(usize, usize, bool, String, usize)> {
+	let mut ret: Vec<_> = moves.drain(..).map(|bw| (bw.coord.0, bw.coord.1, bw.vertical, bw.word, bw.score)).collect();
+	ret.sort();
+	return ret;
+}
+
+#[test]
+fn gaddag_words() {
+	let mut gaddag = str_tree::Gaddag::build_dict_from_file("src/test/words.txt").expect("File not found");
+	assert!(gaddag.is_word("arbre"));
+	assert!(gaddag.is_word("bar"));
+	assert!(gaddag.is_word("cenestpasunmotduscrabble"));
+	assert!(!gaddag.is_word("ar"));
+	assert!(!gaddag.is_word("rbre"));
+	assert!(!gaddag.is_word(""));
+
+	assert!(!gaddag.is_word("erreur"));
+	gaddag.add_word("erreur");
+	assert!(gaddag.is_word("erreur"));
+}
+
+#[test]
+fn gaddag_same_moves_as_tree() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let gaddag = str_tree::Gaddag::build_dict_from_file("src/test/words.txt").expect("File not found");
+
+	let board1 = board::deserialize(&reference_board_message()).expect("Error when deserializing board message");
+
+	// A single word across the start square
+	let mut str_board = "_".repeat(225);
+	str_board.replace_range(7 * 15 + 7..7 * 15 + 12, "arbre");
+	let board2 = board::deserialize(&str_board).expect("Error when deserializing board message");
+
+	for board in [&board1, &board2] {
+		for letter_set in ["", "arbre", "arbr0", "re", "ab0", "zzz"] {
+			let tree_moves: Vec<solver::BestWord> = solver::generate_moves(letter_set, board, &tree)
+				.collect::<Result<_, _>>()
+				.unwrap();
+			let gaddag_moves = solver::find_anchored_words(letter_set, board, &gaddag).unwrap();
+			assert_eq!(sorted_moves(tree_moves), sorted_moves(gaddag_moves));
+
+			let tree_best = solver::find_best_word::<WithoutTimer, _, _>(letter_set, board, &tree, None).unwrap();
+			let gaddag_best = solver::find_best_anchored_word(letter_set, board, &gaddag).unwrap();
+			assert_eq!(tree_best.map(|bw| bw.score), gaddag_best.map(|bw| bw.score));
+		}
+	}
+
+	let empty_board = board::deserialize(&"_".repeat(225)).expect("Error when deserializing board message");
+	for letter_set in ["arbre", "arbr0", "mazout0"] {
+		let tree_moves = solver::find_top_first_words(letter_set, &empty_board, &tree, 1000, None).unwrap();
+		let gaddag_moves = solver::find_anchored_words(letter_set, &empty_board, &gaddag).unwrap();
+		assert_eq!(sorted_moves(tree_moves), sorted_moves(gaddag_moves));
+	}
+
+	// Start square on the edge of the board
+	let layout = std::sync::Arc::new(Layout::new(7, &"_".repeat(49), (3, 0)).unwrap());
+	let edge_board = board::Board::new_empty(&std::sync::Arc::new(Alphabet::default()), &layout);
+	for letter_set in ["arbre", "arbr0"] {
+		let tree_moves = solver::find_top_first_words(letter_set, &edge_board, &tree, 1000, None).unwrap();
+		let gaddag_moves = solver::find_anchored_words(letter_set, &edge_board, &gaddag).unwrap();
+		assert!(!tree_moves.is_empty());
+		assert_eq!(sorted_moves(tree_moves), sorted_moves(gaddag_moves));
+	}
+}
+
+// Compares the time the trie and the GADDAG take to find every move of the test positions. It needs a real lexicon:
+// RSSCRABBLE_LEXICON=words.txt cargo test --release gaddag_speedup -- --ignored --nocapture
+#[test]
+#[ignore]
+fn gaddag_speedup() {
+	let filename = std::env::var("RSSCRABBLE_LEXICON").unwrap_or_else(|_| "src/test/words.txt".to_string());
+	let tree = str_tree::build_dict_from_file(&filename).expect("File not found");
+	let gaddag = str_tree::Gaddag::build_dict_from_file(&filename).expect("File not found");
+
+	let mut across_start = "_".repeat(225);
+	across_start.replace_range(7 * 15 + 7..7 * 15 + 12, "arbre");
+	let positions = [
+		(reference_board_message(), "syste00"),
+		(reference_board_message(), "arbre"),
+		(across_start, "arbr0es")];
+	for (str_board, letter_set) in positions {
+		let board = board::deserialize(&str_board).expect("Error when deserializing board message");
+		let (tree_moves, tree_duration) = average_duration(|| solver::generate_moves(letter_set, &board, &tree).count());
+		let (gaddag_moves, gaddag_duration) = average_duration(
+			|| solver::find_anchored_words(letter_set, &board, &gaddag).unwrap().len());
+		assert_eq!(tree_moves, gaddag_moves);
+		println!("{}: {} moves, trie {:.2?}, GADDAG {:.2?}, {:.1}x faster",
+			letter_set, tree_moves, tree_duration, gaddag_duration, tree_duration.as_secs_f64() / gaddag_duration.as_secs_f64());
+	}
+}
+
+// Result of a search, with its duration averaged over several runs
+fn average_duration<F: Fn() -> usize>(search: F) -> (usize, std::time::Duration) {
+	let nb_runs = 10;
+	let now = std::time::Instant::now();
+	let mut result = 0;
+	for _ in 0..nb_runs {
+		result = search();
+	}
+	return (result, now.elapsed() / nb_runs);
+}
+
+use crate::str_tree::DictNode;
+
+#[test]
+fn dawg_same_words_as_tree() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let dawg = str_tree::Dawg::compile(&tree);
+
+	// The 'z' suffixes save 8 + 7 nodes, 'barre' shares 're' with 'arbre', and the final 'e' is shared once more
+	assert_eq!(dawg.saved_nodes(), 18);
+	assert_eq!(dawg.nb_nodes() + dawg.saved_nodes(), 66);
+
+	for word in ["arbre", "bar", "barre", "mazout", "cenestpasunmotduscrabble", "rzzzzzzzz", "bezzzzzzz"] {
+		assert!(dawg.is_word(word));
+	}
+	for word in ["", "a", "ar", "arbres", "erreur", "zzzzzzzz", "bzzzzzzzz"] {
+		assert!(!dawg.is_word(word));
+	}
+
+	let mut tree_words = str_tree::initiate_word_buf(0);
+	let mut dawg_words = str_tree::initiate_word_buf(0);
+	for letters in ["arbre", "arbr0", "rbre", "00", ""] {
+		tree.get_anagrams(letters, &mut tree_words, None, None, None).unwrap();
+		dawg.get_anagrams(letters, &mut dawg_words, None, None, None).unwrap();
+		assert_eq!(to_string_vec(&tree_words), to_string_vec(&dawg_words));
+
+		let constraints = Some(vec![(2, crate::constraints::WordToFill::new("ba".to_string(),"re".to_string()).unwrap())]);
+		tree.get_anagrams(letters, &mut tree_words, None, Some(vec![(1, 'a')]), constraints.clone()).unwrap();
+		dawg.get_anagrams(letters, &mut dawg_words, None, Some(vec![(1, 'a')]), constraints.clone()).unwrap();
+		assert_eq!(to_string_vec(&tree_words), to_string_vec(&dawg_words));
+	}
+}
+
+#[test]
+fn dawg_add_word() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let mut dawg = str_tree::Dawg::compile(&tree);
+
+	assert!(!dawg.is_word("erreur"));
+	dawg.add_word("erreur");
+	assert!(dawg.is_word("erreur"));
+	assert!(dawg.is_word("arbre"));
+
+	let rebuilt_tree = dawg.to_str_tree();
+	let mut words = (&rebuilt_tree).words();
+	words.sort();
+	assert_eq!(words, vec!["arbre", "bar", "barre", "bezzzzzzz", "cenestpasunmotduscrabble", "erreur", "ezzzzzzzz", "mazout", "rzzzzzzzz"]);
+}
+
+#[test]
+fn compiled_lexicon_round_trip() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let dawg = str_tree::Dawg::compile(&tree);
+
+	let filename = std::env::temp_dir().join("rsScrabble_compiled_lexicon_round_trip.dawg");
+	str_tree::write_compiled(&dawg, &filename).unwrap();
+	let loaded = str_tree::read_compiled(&filename).unwrap();
+	std::fs::remove_file(&filename).unwrap();
+
+	assert_eq!(loaded.nb_nodes(), dawg.nb_nodes());
+	assert_eq!(loaded.saved_nodes(), dawg.saved_nodes());
+	let original_tree = dawg.to_str_tree();
+	let loaded_tree = loaded.to_str_tree();
+	let mut original_words = (&original_tree).words();
+	let mut loaded_words = (&loaded_tree).words();
+	original_words.sort();
+	loaded_words.sort();
+	assert_eq!(original_words, loaded_words);
+	assert!(loaded.is_word("cenestpasunmotduscrabble"));
+	assert!(!loaded.is_word("erreur"));
+}
+
+#[test]
+fn compiled_lexicon_errors() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let dawg = str_tree::Dawg::compile(&tree);
+	let filename = std::env::temp_dir().join("rsScrabble_compiled_lexicon_errors.dawg");
+
+	str_tree::write_compiled(&dawg, &filename).unwrap();
+	let mut bytes = std::fs::read(&filename).unwrap();
+	bytes[20] ^= 1;
+	std::fs::write(&filename, &bytes).unwrap();
+	let err = str_tree::read_compiled(&filename).expect_err("Unlikely success");
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+	assert!(err.to_string().contains("checksum"));
+
+	let err = str_tree::read_compiled("src/test/words.txt").expect_err("Unlikely success");
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+	// Consistent checksums, but inconsistent contents
+	let original = dawg_bytes(&dawg, &filename);
+	let alphabet_size = u16::from_le_bytes([original[10], original[11]]) as usize;
+	let trie_nodes = 12 + 4 * alphabet_size;
+	let nodes = trie_nodes + 8;
+
+	let mut bytes = original.clone();
+	bytes[trie_nodes..trie_nodes + 4].copy_from_slice(&(dawg.nb_nodes() as u32 - 1).to_le_bytes());
+	let err = read_resigned(&mut bytes, &filename);
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+	assert!(err.to_string().contains("fewer trie nodes"));
+
+	// The first child of the root being its own child, which would make a cycle
+	let mut bytes = original.clone();
+	let children_mask = (1 << 22) - 1;
+	let first_child = u32::from_le_bytes(original[nodes..nodes + 4].try_into().unwrap()) & children_mask;
+	let offset = nodes + 4 * first_child as usize;
+	let packed = u32::from_le_bytes(original[offset..offset + 4].try_into().unwrap());
+	bytes[offset..offset + 4].copy_from_slice(&(packed & !children_mask | first_child).to_le_bytes());
+	let err = read_resigned(&mut bytes, &filename);
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+	assert!(err.to_string().contains("children not before their parent"));
+
+	std::fs::remove_file(&filename).unwrap();
+	assert!(str_tree::read_compiled(&filename).is_err());
+}
+
+// Writes a compiled lexicon and returns its bytes
+fn dawg_bytes(dawg: &str_tree::Dawg, filename: &std::path::Path) -> Vec<u8> {
+	str_tree::write_compiled(dawg, filename).unwrap();
+	return std::fs::read(filename).unwrap();
+}
+
+// Writes the bytes of a compiled lexicon with a fresh checksum, and returns the error reading them
+fn read_resigned(bytes: &mut Vec<u8>, filename: &std::path::Path) -> std::io::Error {
+	bytes.truncate(bytes.len() - 4);
+	let mut hash: u32 = 0x811c9dc5;
+	for byte in bytes.iter() {
+		hash = (hash ^ *byte as u32).wrapping_mul(0x01000193);
+	}
+	bytes.extend_from_slice(&hash.to_le_bytes());
+	std::fs::write(filename, &bytes).unwrap();
+	return str_tree::read_compiled(filename).expect_err("Unlikely success");
+}
+
+#[test]
+fn kwg_round_trip() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let dawg = str_tree::Dawg::compile(&tree);
+
+	let filename = std::env::temp_dir().join("rsScrabble_kwg_round_trip.kwg");
+	str_tree::write_kwg(&dawg, &filename, &str_tree::Alphabet::default().letters()).unwrap();
+	let loaded = str_tree::read_kwg(&filename, &str_tree::Alphabet::default().letters()).unwrap();
+	std::fs::remove_file(&filename).unwrap();
+
+	assert_eq!(loaded.nb_nodes(), dawg.nb_nodes());
+	assert_eq!(loaded.saved_nodes(), dawg.saved_nodes());
+	let loaded_tree = loaded.to_str_tree();
+	let mut words = (&loaded_tree).words();
+	words.sort();
+	assert_eq!(words, vec!["arbre", "bar", "barre", "bezzzzzzz", "cenestpasunmotduscrabble", "ezzzzzzzz", "mazout", "rzzzzzzzz"]);
+
+	let mut tree_words = str_tree::initiate_word_buf(0);
+	let mut kwg_words = str_tree::initiate_word_buf(0);
+	tree.get_anagrams("arbr0", &mut tree_words, None, None, None).unwrap();
+	loaded.get_anagrams("arbr0", &mut kwg_words, None, None, None).unwrap();
+	assert!(unordered_equal(&to_string_vec(&tree_words), &to_string_vec(&kwg_words)));
+}
+
+#[test]
+fn kwg_layout() {
+	// "ab" and "b" with a GADDAG part, as written by wolges: the GADDAG part must be skipped
+	let nodes: Vec<u32> = vec![
+		0x00400002, // DAWG root
+		0x00400005, // GADDAG root
+		0x01000004, // a
+		0x02c00000, // b, accepts, end
+		0x02c00000, // b, accepts, end
+		0x02400006, // GADDAG: b, end
+		0x00c00000, // GADDAG: separator, accepts, end
+	];
+	let bytes: Vec<u8> = nodes.iter().flat_map(|node| node.to_le_bytes()).collect();
+	let filename = std::env::temp_dir().join("rsScrabble_kwg_layout.kwg");
+	std::fs::write(&filename, &bytes).unwrap();
+	let dawg = str_tree::read_kwg(&filename, &str_tree::Alphabet::default().letters()).unwrap();
+	std::fs::remove_file(&filename).unwrap();
+
+	assert!(dawg.is_word("ab"));
+	assert!(dawg.is_word("b"));
+	assert!(!dawg.is_word("a"));
+	assert!(!dawg.is_word("ba"));
+	assert_eq!(dawg.nb_nodes(), 3);
+}
+
+#[test]
+fn remove_word() {
+	let mut tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	assert!(tree.remove_word("bar"));
+	assert!(!tree.is_word("bar"));
+	assert!(tree.is_word("barre"));
+	assert!(!tree.remove_word("bar"));
+	assert!(!tree.remove_word("ba"));
+
+	// The branch of "mazout" leads nowhere anymore, and must be pruned
+	assert!(tree.remove_word("mazout"));
+	assert!((&tree).child('m').is_none());
+	assert!(tree.remove_word("barre"));
+	assert!((&tree).walk("bar").is_none());
+	assert!(tree.is_word("bezzzzzzz"));
+
+	let mut dawg = str_tree::Dawg::compile(&str_tree::build_dict_from_file("src/test/words.txt").unwrap());
+	assert!(dawg.remove_word("arbre"));
+	assert!(!dawg.is_word("arbre"));
+	assert!(!dawg.remove_word("arbre"));
+	assert!(dawg.is_word("barre"));
+
+	let mut gaddag = str_tree::Gaddag::build_dict_from_file("src/test/words.txt").unwrap();
+	assert!(AnchoredDictionnary::remove_word(&mut gaddag, "bar"));
+	assert!(!AnchoredDictionnary::is_word(&gaddag, "bar"));
+	assert!(AnchoredDictionnary::is_word(&gaddag, "barre"));
+}
+
+#[test]
+fn blacklist() {
+	assert_eq!(str_tree::read_words("src/test/blacklist.txt", Some(&str_tree::Alphabet::default()), true).unwrap(), vec!["barre", "mazout", "notindict"]);
+
+	let mut tree = str_tree::build_dict_from_file("src/test/words.txt").unwrap();
+	assert_eq!(tree.apply_blacklist("src/test/blacklist.txt", &str_tree::Alphabet::default()).unwrap(), 2);
+	assert!(!tree.is_word("barre"));
+	assert!(!tree.is_word("mazout"));
+	assert!(tree.is_word("bar"));
+
+	let mut lexicon = str_tree::Lexicon::Tree(str_tree::build_dict_from_file("src/test/words.txt").unwrap());
+	lexicon.compress();
+	assert_eq!(lexicon.apply_blacklist("src/test/blacklist.txt", &str_tree::Alphabet::default()).unwrap(), 2);
+	assert!(!lexicon.is_word("barre"));
+	assert!(lexicon.is_word("arbre"));
+
+	assert!(tree.apply_blacklist("src/test/not_a_file.txt", &str_tree::Alphabet::default()).is_err());
+}
+
+#[test]
+fn word_list_lines() {
+	let filename = std::env::temp_dir().join("rsScrabble_word_list_lines.txt");
+	std::fs::write(&filename, "bar\n\n c'est \n# note\n").unwrap();
+	assert_eq!(str_tree::read_words(&filename, None, false).unwrap(), vec!["bar", "c'est", "# note"]);
+	assert_eq!(str_tree::read_words(&filename, None, true).unwrap(), vec!["bar", "c'est"]);
+	let err = str_tree::read_words(&filename, Some(&str_tree::Alphabet::default()), true).unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+	// Without an alphabet, the lines it cannot read are still loaded
+	let tree = str_tree::build_dict_from_file(filename.to_str().unwrap()).unwrap();
+	assert!(tree.is_word("bar"));
+	assert!(tree.is_word("c'est"));
+	let gaddag = str_tree::Gaddag::build_dict_from_file(filename.to_str().unwrap()).unwrap();
+	assert!(AnchoredDictionnary::is_word(&gaddag, "c'est"));
+	std::fs::remove_file(&filename).unwrap();
+}
+
+#[test]
+fn multi_lexicon() {
+	let mut multi = str_tree::MultiLexicon::new();
+	let main = multi.add_lexicon("main", "src/test/words.txt", &str_tree::Alphabet::default()).unwrap();
+	let house = multi.add_lexicon("house", "src/test/words_house.txt", &str_tree::Alphabet::default()).unwrap();
+	assert!(multi.add_lexicon("house", "src/test/words_house.txt", &str_tree::Alphabet::default()).is_err());
+	assert_eq!(multi.all(), main | house);
+
+	assert_eq!(multi.lexicons_of("bar"), main | house);
+	assert_eq!(multi.lexicons_of("barre"), main);
+	assert_eq!(multi.lexicons_of("rab"), house);
+	assert_eq!(multi.lexicons_of("ba"), 0);
+	assert!(multi.is_word("rab"));
+	assert!(!multi.select(main).is_word("rab"));
+	assert!(multi.select(house).is_word("rab"));
+
+	let mut words = str_tree::initiate_word_buf(0);
+	multi.select(house).get_anagrams("arbres", &mut words, None, None, None).unwrap();
+	assert!(unordered_equal(&to_string_vec(&words), &to_string_vec_from(&["bar", "rab", "bras"])));
+	multi.select(main).get_anagrams("arbres", &mut words, None, None, None).unwrap();
+	assert!(unordered_equal(&to_string_vec(&words), &to_string_vec_from(&["arbre", "bar", "barre"])));
+	multi.get_anagrams("arbres", &mut words, None, None, None).unwrap();
+	assert!(unordered_equal(&to_string_vec(&words), &to_string_vec_from(&["arbre", "bar", "barre", "rab", "bras"])));
+
+	assert!(multi.remove_word_from("bar", house));
+	assert!(!multi.remove_word_from("bar", house));
+	assert_eq!(multi.lexicons_of("bar"), main);
+	multi.add_word_to("zebres", main);
+	assert!(multi.remove_word_from("zebre", main | house));
+	assert_eq!(multi.lexicons_of("zebres"), main);
+	assert!(multi.remove_word_from("zebres", main));
+	multi.select(multi.all()).get_anagrams("zebre0", &mut words, None, None, None).unwrap();
+	assert!(to_string_vec(&words).iter().all(|word| !word.starts_with('z')));
+}
+
+#[test]
+fn multi_lexicon_solver() {
+	let mut multi = str_tree::MultiLexicon::new();
+	let main = multi.add_lexicon("main", "src/test/words.txt", &str_tree::Alphabet::default()).unwrap();
+	let house = multi.add_lexicon("house", "src/test/words_house.txt", &str_tree::Alphabet::default()).unwrap();
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").unwrap();
+
+	let mut str_board = String::new();
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______bar_____");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	let b = board::deserialize(&str_board).unwrap();
+
+	let from_tree = solver::find_top_words("arbre", &b, &tree, 1000).unwrap();
+	let from_main = solver::find_top_words("arbre", &b, &multi.select(main), 1000).unwrap();
+	assert_eq!(sorted_moves(from_tree), sorted_moves(from_main));
+
+	let empty = board::deserialize(&"_".repeat(225)).unwrap();
+	let mut words: Vec<String> = solver::find_top_first_words("sarb", &empty, &multi.select(house), 1000, None).unwrap()
+		.iter().map(|bw| bw.word.clone()).collect();
+	words.sort();
+	words.dedup();
+	assert_eq!(words, vec!["bar", "bras", "rab"]);
+
+	let lexicon = str_tree::Lexicon::Multi(multi);
+	assert_eq!(lexicon.lexicons_of("bar"), vec!["main", "house"]);
+	assert_eq!(lexicon.lexicons_of("rab"), vec!["house"]);
+	assert!(lexicon.select(Some(&["house".to_string()])).unwrap().is_word("rab"));
+	assert!(!lexicon.select(Some(&["main".to_string()])).unwrap().is_word("rab"));
+	assert!(lexicon.select(None).unwrap().is_word("rab"));
+	assert_eq!(lexicon.select(Some(&["twl".to_string()])).err(), Some("twl".to_string()));
+}
+
+#[test]
+fn match_pattern() {
+	let mut tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	assert_eq!(tree.match_pattern("?A?", None).unwrap(), vec!["bar"]);
+	assert_eq!(tree.match_pattern("b*", None).unwrap(), vec!["bar", "barre", "bezzzzzzz"]);
+	assert_eq!(tree.match_pattern("*e", None).unwrap(), vec!["arbre", "barre", "cenestpasunmotduscrabble"]);
+	// The words come in the order of the dictionnary graph
+	assert_eq!(tree.match_pattern("*zzz**", None).unwrap(), vec!["bezzzzzzz", "rzzzzzzzz", "ezzzzzzzz"]);
+	assert_eq!(tree.match_pattern("bar", None).unwrap(), vec!["bar"]);
+	assert!(tree.match_pattern("ba", None).unwrap().is_empty());
+	// Longer than the board
+	assert_eq!(tree.match_pattern("c*", None).unwrap(), vec!["cenestpasunmotduscrabble"]);
+	assert_eq!(tree.match_pattern("*", None).unwrap().len(), 8);
+
+	// The whole word is taken from the rack, the letters of the pattern included
+	assert_eq!(tree.match_pattern("?A??E*", None).unwrap(), vec!["barre"]);
+	assert_eq!(tree.match_pattern("?A??E*", Some("ERRAB")).unwrap(), vec!["barre"]);
+	assert!(tree.match_pattern("?A??E*", Some("RRB")).unwrap().is_empty());
+	assert_eq!(tree.match_pattern("?A??E*", Some("ER?AB")).unwrap(), vec!["baRre", "barRe"]);
+	assert_eq!(tree.match_pattern("?A??E*", Some("ERR?B")).unwrap(), vec!["bArre"]);
+	assert_eq!(tree.match_pattern("b*", Some("ZERRAB")).unwrap(), vec!["bar", "barre"]);
+
+	match tree.match_pattern("b#r", None) {
+		Err(WordError::UnknownChar(_)) => (),
+		_ => panic!("Unexpected result")
+	};
+
+	tree.add_word("abcdefghijklmno");
+	assert_eq!(tree.match_pattern("a*", None).unwrap(), vec!["arbre", "abcdefghijklmno"]);
+	assert_eq!(tree.match_pattern("*o", None).unwrap(), vec!["abcdefghijklmno"]);
+}
+
+#[test]
+fn hooks() {
+	let mut tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	assert_eq!(tree.hooks("arre").unwrap(), ("b".to_string(), "".to_string()));
+	assert_eq!(tree.hooks("bar").unwrap(), ("".to_string(), "".to_string()));
+	tree.add_word("bars");
+	tree.add_word("bard");
+	tree.add_word("ar");
+	assert_eq!(tree.hooks("bar").unwrap(), ("".to_string(), "sd".to_string()));
+	assert_eq!(tree.hooks("ar").unwrap(), ("b".to_string(), "".to_string()));
+	assert_eq!(tree.hooks("zzzzzzzz").unwrap(), ("re".to_string(), "".to_string()));
+	assert!(tree.hooks("b?r").is_err());
+}
+
+use crate::alphabet::{Alphabet, AlphabetError, to_blank};
+
+#[test]
+fn sort_words() {
+	let alphabet = spanish_alphabet();
+	let mut words: Vec<String> = ["rr", "r", "[CH]", "ch", "c", "cA", "ca"].iter().map(|word| alphabet.encode_with_blanks(word, |_| false).unwrap()).collect();
+	alphabet.sort_words(&mut words);
+	let words: Vec<String> = words.iter().map(|word| alphabet.decode(word)).collect();
+	assert_eq!(words, vec!["c", "ca", "cA", "[ch]", "[CH]", "r", "[rr]"]);
+}
+
+fn spanish_alphabet() -> Alphabet {
+	let tiles = [("a", 1), ("c", 3), ("ch", 5), ("e", 1), ("l", 1), ("ll", 8), ("m", 3), ("n", 1), ("ñ", 8), ("o", 1), ("p", 3), ("r", 1), ("rr", 8), ("s", 1)];
+	let tiles: Vec<(String, usize)> = tiles.iter().map(|(text, value)| (text.to_string(), *value)).collect();
+	return Alphabet::new(&tiles).unwrap();
+}
+
+#[test]
+fn alphabet() {
+	let spanish = spanish_alphabet();
+	let chorro = spanish.encode("chorro", |_| false).unwrap();
+	assert_eq!(chorro.chars().count(), 4);
+	assert_eq!(spanish.encode("CHORRO", |_| false).unwrap(), chorro);
+	assert_eq!(spanish.encode("[ch]o[rr]o", |_| false).unwrap(), chorro);
+	assert_eq!(spanish.decode(&chorro), "[ch]o[rr]o");
+	// Read greedily: "carro" is c, a, rr, o
+	assert_eq!(spanish.encode("carro", |_| false).unwrap().chars().count(), 4);
+	assert_eq!(spanish.encode("[c]a[r][r]o", |_| false).unwrap().chars().count(), 5);
+	assert_eq!(spanish.str_value(&chorro).unwrap(), 15);
+	assert!(spanish.encode("zorro", |_| false).is_err());
+	assert!(spanish.encode("[ch", |_| false).is_err());
+
+	let ch = chorro.chars().next().unwrap();
+	assert_eq!(spanish.value(to_blank(ch)).unwrap(), 0);
+	assert_eq!(spanish.display(to_blank(ch)).unwrap(), "[CH]");
+	assert_eq!(spanish.parse_tile("CH"), Some(to_blank(ch)));
+	assert_eq!(spanish.parse_tile("Ñ"), Some('Ñ'));
+
+	let polish_tiles: Vec<(String, usize)> = vec![("a".to_string(), 1), ("ą".to_string(), 5), ("Ł".to_string(), 3)];
+	let polish = Alphabet::new(&polish_tiles).unwrap();
+	assert_eq!(polish.letters(), vec!['a', 'ą', 'ł']);
+	assert_eq!(polish.value('ł').unwrap(), 3);
+	assert_eq!(polish.value('Ł').unwrap(), 0);
+	assert_eq!(polish.decode("ąŁa"), "ąŁa");
+	assert!(polish.value('b').is_err());
+
+	assert!(Alphabet::new(&[("a".to_string(), 1), ("A".to_string(), 1)]).is_err());
+	assert!(Alphabet::new(&[("a1".to_string(), 1)]).is_err());
+
+	// Tiles of more than one letter each take a spare letter, unless it is a tile itself
+	let mut pairs: Vec<(String, usize)> = ('a'..='y').map(|c| (format!("{}{}", c, c), 1)).collect();
+	assert!(Alphabet::new(&pairs[..24]).is_ok());
+	assert!(matches!(Alphabet::new(&pairs), Err(AlphabetError::TooManyTiles(_))));
+	pairs.truncate(24);
+	pairs.push(("ω".to_string(), 1));
+	assert!(matches!(Alphabet::new(&pairs), Err(AlphabetError::TooManyTiles(_))));
+	assert_eq!(Alphabet::default().letters(), ('a'..='z').collect::<Vec<char>>());
+}
+
+#[test]
+fn alphabet_board() {
+	let spanish = std::sync::Arc::new(spanish_alphabet());
+	let layout = std::sync::Arc::new(board::Layout::default());
+	let tree = str_tree::build_dict_with_alphabet("src/test/words_spanish.txt", &spanish).expect("File not found");
+	assert!(tree.is_word(&spanish.encode("año", |_| false).unwrap()));
+	assert!(tree.is_word(&spanish.encode("llama", |_| false).unwrap()));
+	assert!(!tree.is_word(&spanish.encode("lama", |_| false).unwrap()));
+
+	let mut str_board = String::new();
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_____[ch]o[RR]o______");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	str_board.push_str("_______________");
+	let board = board::deserialize_with(&str_board, &spanish, &layout).expect("Error when deserializing board message");
+	assert!(board.serialize::<NotTransposed>().contains("[ch]o[RR]o"));
+	assert!(board.pretty::<NotTransposed>().contains("[ch] o [RR] o"));
+	assert!(board::deserialize_with("[ch", &spanish, &layout).is_err());
+
+	let empty = board::deserialize_with(&"_".repeat(225), &spanish, &layout).unwrap();
+	let chorro: Vec<char> = spanish.encode("chorro", |_| false).unwrap().chars().collect();
+	assert_eq!(15, empty.get_score::<NotTransposed>(&chorro, 7, 7).unwrap());
+	let blank_ch = [to_blank(chorro[0]), chorro[1], chorro[2], chorro[3]];
+	assert_eq!(10, empty.get_score::<NotTransposed>(&blank_ch, 7, 7).unwrap());
+
+	let rack = spanish.encode("rroocha", |_| false).unwrap();
+	let top = solver::find_top_first_words(&rack, &empty, &tree, 1, None).unwrap();
+	assert_eq!(spanish.decode(&top[0].word), "[ch]o[rr]o");
+}
+
+use crate::tile_set::TileSet;
+
+#[test]
+fn tile_set_presets() {
+	let totals = [("english", 100), ("french", 102), ("german", 102), ("spanish", 100), ("italian", 120), ("dutch", 102), ("polish", 100)];
+	assert_eq!(TileSet::preset_names(), totals.iter().map(|(name, _)| *name).collect::<Vec<&str>>());
+	for (name, total) in totals {
+		let tile_set = TileSet::preset(name).unwrap();
+		assert_eq!(tile_set.total(), total, "{}", name);
+		assert_eq!(tile_set.blanks(), 2);
+	}
+	assert!(TileSet::preset("klingon").is_none());
+	assert_eq!(TileSet::preset("French").unwrap().alphabet(), &Alphabet::default());
+
+	let spanish = TileSet::preset("spanish").unwrap();
+	for (tile, value) in [("ch", 5), ("ll", 8), ("rr", 8), ("ñ", 8)] {
+		let c = spanish.alphabet().parse_tile(tile).unwrap();
+		assert_eq!(spanish.count(c), 1);
+		assert_eq!(spanish.alphabet().value(c).unwrap(), value);
+	}
+	assert_eq!(spanish.alphabet().encode("chorro", |_| false).unwrap().chars().count(), 4);
+	assert_eq!(spanish.count('k'), 0);
+
+	let polish = TileSet::preset("polish").unwrap();
+	assert_eq!(polish.count('ł'), 2);
+	assert_eq!(polish.alphabet().value('ź').unwrap(), 9);
+}
+
+#[test]
+fn tile_set_scoring() {
+	let english = std::sync::Arc::new(TileSet::preset("english").unwrap().alphabet().clone());
+	let french = std::sync::Arc::new(TileSet::preset("french").unwrap().alphabet().clone());
+	let layout = std::sync::Arc::new(board::Layout::default());
+	let english_board = board::deserialize_with(&"_".repeat(225), &english, &layout).unwrap();
+	let french_board = board::deserialize_with(&"_".repeat(225), &french, &layout).unwrap();
+	// K is worth 5 in english, 10 in french
+	assert_eq!(16, english_board.get_score::<NotTransposed>(&['k','a','y','a','k'], 7, 7).unwrap());
+	assert_eq!(32, french_board.get_score::<NotTransposed>(&['k','a','y','a','k'], 7, 7).unwrap());
+}
+
+use crate::board::{Layout, LayoutError};
+
+#[test]
+fn layouts() {
+	assert_eq!(Layout::preset_names(), vec!["scrabble", "words_with_friends", "super_scrabble"]);
+	for (name, side) in [("scrabble", 15), ("words_with_friends", 15), ("super_scrabble", 21)] {
+		let layout = Layout::preset(name).unwrap();
+		assert_eq!(layout.side(), side);
+		assert_eq!(layout.start(), (side / 2, side / 2));
+	}
+	assert!(Layout::preset("monopoly").is_none());
+	assert_eq!(Layout::plain(11).unwrap().start(), (5, 5));
+
+	assert!(matches!(Layout::plain(22), Err(LayoutError::WrongSize(_))));
+	assert!(matches!(Layout::new(3, "_________", (3, 0)), Err(LayoutError::StartOutside(_))));
+	assert!(matches!(Layout::new(3, "________", (1, 1)), Err(LayoutError::WrongSize(_))));
+	assert!(matches!(Layout::new(3, "____8____", (1, 1)), Err(LayoutError::UnknownSymbol(_))));
+	assert!(Layout::new(3, "7_4______", (1, 1)).is_ok());
+}
+
+#[test]
+fn super_scrabble_board() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let gaddag = str_tree::Gaddag::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(Layout::preset("super_scrabble").unwrap());
+
+	// Quadruple word on the corner, double letter on the second r
+	let empty = board::deserialize_with(&"_".repeat(441), &alphabet, &layout).unwrap();
+	assert_eq!(32, empty.get_score::<NotTransposed>(&['a','r','b','r','e'], 0, 0).unwrap());
+	assert!(board::deserialize_with(&"_".repeat(225), &alphabet, &layout).is_err());
+	assert!(empty.serialize::<NotTransposed>().starts_with("7__2___6"));
+	assert!(empty.pretty::<NotTransposed>().lines().nth(1).unwrap().starts_with(" 1 7 _ _ 2 _ _ _ 6"));
+
+	let first = solver::find_best_first_word("arbre", &empty, &tree, None).unwrap().unwrap();
+	assert_eq!(first.coord.0, 10);
+	assert!(first.coord.1 < 10 && first.coord.1 + 5 > 10);
+
+	let mut str_board = String::new();
+	for x in 0..21 {
+		match x {
+			16 => str_board.push_str("________________bar__"),
+			_ => str_board.push_str(&"_".repeat(21))
+		};
+	}
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+	for letter_set in ["arbre", "arbr0", "re"] {
+		let tree_moves: Vec<solver::BestWord> = solver::generate_moves(letter_set, &board, &tree)
+			.collect::<Result<_, _>>()
+			.unwrap();
+		let gaddag_moves = solver::find_anchored_words(letter_set, &board, &gaddag).unwrap();
+		assert!(!tree_moves.is_empty());
+		assert!(tree_moves.iter().any(|bw| bw.coord.0 > 14 || bw.coord.1 > 14));
+		assert_eq!(sorted_moves(tree_moves), sorted_moves(gaddag_moves));
+	}
+}
+
+#[test]
+fn small_board() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(Layout::plain(11).unwrap());
+
+	let empty = board::deserialize_with(&"_".repeat(121), &alphabet, &layout).unwrap();
+	let first = solver::find_best_first_word("arbre", &empty, &tree, None).unwrap().unwrap();
+	assert_eq!(first.coord.0, 5);
+	// 10 letter words don't fit around the start square
+	assert!(solver::find_top_first_words("zzzzzzzze0", &empty, &tree, 10, None).unwrap().iter().all(|bw| bw.word.len() < 10));
+
+	let mut str_board = "_".repeat(55);
+	str_board.push_str("_arbre_____");
+	str_board.push_str(&"_".repeat(55));
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+	let best = solver::find_best_word::<WithoutTimer, _, _>("bar", &board, &tree, None).unwrap().unwrap();
+	assert!(best.coord.0 < 11 && best.coord.1 < 11);
+	assert_eq!(solver::generate_moves("barre", &board, &tree).count(), 
+		solver::find_top_words("barre", &board, &tree, 1000).unwrap().len());
+
+	// First words may start on the start square, even in the first column
+	let layout = std::sync::Arc::new(Layout::new(5, &"_".repeat(25), (2, 0)).unwrap());
+	let empty = board::Board::new_empty(&alphabet, &layout);
+	let first = solver::find_best_first_word("arbre", &empty, &tree, None).unwrap().unwrap();
+	assert_eq!(first.coord, (2, 0));
+	assert_eq!(solver::find_top_first_words("arbre", &empty, &tree, 10, None).unwrap().len(), 3);
+}
+
+#[test]
+fn layout_file() {
+	let layout = Layout::from_file("src/test/layout_training.txt").unwrap();
+	assert_eq!(layout.side(), 5);
+	assert_eq!(layout.start(), (2, 2));
+	assert_eq!(layout.rack_size(), 5);
+	assert_eq!(layout.bingo_bonus(), 20);
+	assert_eq!(Layout::parse(&layout.to_text()).unwrap(), layout);
+	for name in Layout::preset_names() {
+		let preset = Layout::preset(name).unwrap();
+		assert_eq!(Layout::parse(&preset.to_text()).unwrap(), preset);
+	}
+	assert_eq!(Layout::preset("words_with_friends").unwrap().bingo_bonus(), 35);
+	assert_eq!(Layout::parse("___\n___\n___").unwrap(), Layout::plain(3).unwrap());
+
+	assert!(matches!(Layout::from_file("src/test/not_a_file.txt"), Err(LayoutError::UnreadableFile(_))));
+	assert!(matches!(Layout::parse("rack seven\n___\n___\n___"), Err(LayoutError::WrongMetadata(_))));
+	assert!(matches!(Layout::parse("start 1\n___\n___\n___"), Err(LayoutError::WrongMetadata(_))));
+	assert!(matches!(Layout::parse("rack 0\n___\n___\n___"), Err(LayoutError::WrongMetadata(_))));
+	assert!(matches!(Layout::parse("___\n__\n___"), Err(LayoutError::WrongSize(_))));
+	assert!(matches!(Layout::parse("start 3 3\n___\n___\n___"), Err(LayoutError::StartOutside(_))));
+
+	// Tiles only messages take the premium squares of the layout, and the rack size gives the bingos
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(layout);
+	let empty = board::deserialize_with(&"_".repeat(25), &alphabet, &layout).unwrap();
+	assert_eq!(34, empty.get_score::<NotTransposed>(&['a','r','b','r','e'], 2, 0).unwrap());
+	assert_eq!(34, solver::find_best_first_word("arbre", &empty, &tree, None).unwrap().unwrap().score);
+
+	let mut str_board = String::new();
+	str_board.push_str("_____");
+	str_board.push_str("_____");
+	str_board.push_str("_bar_");
+	str_board.push_str("_____");
+	str_board.push_str("_____");
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+	assert!(board.pretty::<NotTransposed>().starts_with("   A B C D E\n 1 6 _ _ _ 6\n 2 _ 2 _ 2 _\n 3 _ b a r _"));
+	// Two triple word squares, and a bingo
+	assert_eq!(83, board.get_score::<NotTransposed>(&['b','a','r','r','e'], 4, 0).unwrap());
+}
+
+#[test]
+fn premiums_under_tiles() {
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(Layout::preset("scrabble").unwrap());
+	let mut str_board = "_".repeat(105);
+	str_board.push_str("_______A_______");
+	str_board.push_str(&"_".repeat(105));
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+
+	// The double word square is still known under the blank, but only counts for new tiles
+	assert_eq!(board.premiums().chars().nth(7*15 + 7), Some('5'));
+	assert_eq!(board.premiums(), Layout::preset("scrabble").unwrap().to_text().lines().skip(3).collect::<String>());
+	assert_eq!(4, board.get_score::<NotTransposed>(&['b','_','r'], 7, 6).unwrap());
+	assert_eq!(4, board.get_score::<Transposed>(&['b','_','r'], 7, 6).unwrap());
+	assert!(board.pretty::<NotTransposed>().contains(" 8 6 _ _ 2 _ _ _ A _ _ _ 2 _ _ 6"));
+
+	// Symbols of the message replace the premiums of the layout
+	let mut str_board = "2".repeat(105);
+	str_board.push_str("_______A_______");
+	str_board.push_str(&"_".repeat(105));
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+	assert!(board.premiums().starts_with(&"2".repeat(105)));
+	assert_eq!(board.premiums().chars().nth(7*15 + 7), Some('5'));
+}
+
+#[test]
+fn apply_and_undo_move() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(Layout::preset("scrabble").unwrap());
+	let mut board = board::deserialize_with(&"_".repeat(225), &alphabet, &layout).unwrap();
+	let empty_message = board.message();
+
+	let first = solver::find_best_first_word("arbre", &board, &tree, None).unwrap().unwrap();
+	assert_eq!(board.apply_move(&first).unwrap(), first.score);
+	assert!(!board.is_empty());
+	assert!(board.message().contains(&first.word));
+	// The same tiles can't be placed twice
+	assert!(matches!(board.apply_move(&first), Err(WordError::TileOccupied(_))));
+
+	let second = solver::find_best_word::<WithoutTimer, _, _>("bar", &board, &tree, None).unwrap().unwrap();
+	assert!(second.word.contains('_'));
+	assert_eq!(board.apply_move(&second).unwrap(), second.score);
+	board.undo_move(&second).unwrap();
+	assert!(matches!(board.undo_move(&second), Err(WordError::MissingTile(_))));
+	board.undo_move(&first).unwrap();
+	assert_eq!(board.message(), empty_message);
+	assert_eq!(board.premiums(), empty_message);
+
+	// Blanks are placed as jokers, and worth nothing
+	let blank_move = solver::BestWord{vertical: true, coord: (5, 7), word: "BAR".to_string(), score: 0, ..Default::default()};
+	assert_eq!(board.apply_move(&blank_move).unwrap(), 0);
+	assert!(board.message().contains('A'));
+	let lower_move = solver::BestWord{vertical: true, coord: (5, 7), word: "bar".to_string(), score: 0, ..Default::default()};
+	assert!(matches!(board.undo_move(&lower_move), Err(WordError::MissingTile(_))));
+	board.undo_move(&blank_move).unwrap();
+
+	let out = solver::BestWord{vertical: false, coord: (7, 13), word: "bar".to_string(), score: 0, ..Default::default()};
+	assert!(matches!(board.apply_move(&out), Err(WordError::OutOfBoard(_))));
+	let through_nothing = solver::BestWord{vertical: false, coord: (7, 6), word: "b_r".to_string(), score: 0, ..Default::default()};
+	assert!(matches!(board.apply_move(&through_nothing), Err(WordError::UnexpectedUnderscore(_))));
+	assert_eq!(board.message(), empty_message);
+
+	let spanish = std::sync::Arc::new(spanish_alphabet());
+	assert_eq!(spanish.encode_with_blanks("[CH]oRRo_", |c| c == '_').unwrap(), 
+		format!("{}o{}o_", to_blank(spanish.parse_tile("ch").unwrap()), to_blank(spanish.parse_tile("rr").unwrap())));
+}
+
+use crate::board::MoveError;
+
+#[test]
+fn validate_move() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+
+	let board = board::deserialize(&reference_board_message()).expect("Error when deserializing board message");
+
+	// The letters played through can be spelled or written `_`
+	assert_eq!(board.validate_move("arbre", (11, 3), false, "arbr", &tree).map(|breakdown| breakdown.total()), Ok(14));
+	let breakdown = board.validate_move("arbr_", (11, 3), false, "rbra", &tree).unwrap();
+	assert_eq!(breakdown, board.get_score_breakdown::<NotTransposed>(&['a','r','b','r','_'], 11, 3).unwrap());
+	assert_eq!((breakdown.main_word.as_str(), breakdown.word_multiplier, breakdown.total()), ("arbre", 2, 14));
+	assert!(board.validate_move("arbR_", (11, 3), false, "arb0", &tree).unwrap().total() < 14);
+
+	assert!(matches!(board.validate_move("arbre", (11, 3), false, "arb0", &tree), Err(MoveError::NotInRack(_))));
+	assert!(matches!(board.validate_move("arbrz", (11, 3), false, "arbrz", &tree), Err(MoveError::TileOccupied(_))));
+	assert!(matches!(board.validate_move("rbr", (11, 4), false, "rbr", &tree), Err(MoveError::NotContiguous(_))));
+	assert!(matches!(board.validate_move("b_r", (0, 0), false, "br", &tree), Err(MoveError::NotContiguous(_))));
+	assert!(matches!(board.validate_move("__", (9, 7), false, "bar", &tree), Err(MoveError::NoTilePlaced(_))));
+	assert!(matches!(board.validate_move("bar", (0, 0), false, "bar", &tree), Err(MoveError::NotConnected(_))));
+	assert!(matches!(board.validate_move("bar", (7, 13), false, "bar", &tree), Err(MoveError::OutOfBoard(_))));
+	assert!(matches!(board.validate_move("rbrae", (11, 3), false, "rbra", &tree), Err(MoveError::NotAWord(_))));
+	// The main word is fine, but its `a` forms `aa` with the `a` of the board
+	assert!(matches!(board.validate_move("bar", (6, 6), true, "bar", &tree), Err(MoveError::NotAWord(_))));
+
+	let empty = board::deserialize(&"_".repeat(225)).unwrap();
+	assert!(matches!(empty.validate_move("bar", (0, 0), false, "bar", &tree), Err(MoveError::StartNotCovered(_))));
+	assert_eq!(empty.validate_move("bar", (7, 6), false, "bar", &tree).unwrap().total(), 5);
+	assert_eq!(empty.validate_move("bar", (6, 7), true, "bar", &tree).unwrap().total(), 5);
+}
+
+#[test]
+fn score_breakdown() {
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(Layout::preset("scrabble").unwrap());
+	let mut str_board = "_".repeat(105);
+	str_board.push_str("______bar______");
+	str_board.push_str(&"_".repeat(105));
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+
+	// Two double letter squares, under the `r` of `br` and the `e` of `re`
+	let breakdown = board.get_score_breakdown::<NotTransposed>(&['b','a','r','r','e'], 8, 4).unwrap();
+	assert_eq!(breakdown, ScoreBreakdown{
+		main_word: "barre".to_string(),
+		base_value: 7,
+		letter_multipliers: vec![1, 1, 2, 1, 2],
+		word_multiplier: 1,
+		main_score: 9,
+		cross_words: vec![("br".to_string(), 5), ("ar".to_string(), 2), ("re".to_string(), 3)],
+		bingo_bonus: 0});
+	assert_eq!(breakdown.total(), 19);
+	assert_eq!(board.get_score::<NotTransposed>(&['b','a','r','r','e'], 8, 4), Ok(19));
+
+	// The tiles played through are spelled in the main word, and the blanks are worth nothing
+	let breakdown = board.get_score_breakdown::<Transposed>(&['_','R','r','e'], 7, 7).unwrap();
+	assert_eq!(breakdown.main_word, "aRre");
+	assert_eq!(breakdown.base_value, 3);
+	assert_eq!(breakdown.cross_words, vec![]);
+
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let top = solver::find_top_words("barre", &board, &tree, 10).unwrap();
+	assert!(!top.is_empty());
+	for bw in top {
+		assert_eq!(bw.score, bw.breakdown.total());
+		assert_eq!(bw.breakdown.main_word.chars().count(), bw.word.chars().count());
+	}
+
+	let layout = std::sync::Arc::new(Layout::from_file("src/test/layout_training.txt").unwrap());
+	let board = board::deserialize_with(&"_".repeat(25), &alphabet, &layout).unwrap();
+	let breakdown = board.get_score_breakdown::<NotTransposed>(&['b','a','r','r','e'], 4, 0).unwrap();
+	assert_eq!((breakdown.word_multiplier, breakdown.main_score, breakdown.bingo_bonus), (9, 63, 20));
+	assert_eq!(breakdown.total(), 83);
+}
+
+#[test]
+fn placed_tiles_and_leave() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let alphabet = std::sync::Arc::new(Alphabet::default());
+	let layout = std::sync::Arc::new(Layout::preset("scrabble").unwrap());
+	let mut str_board = "_".repeat(105);
+	str_board.push_str("______bAr______");
+	str_board.push_str(&"_".repeat(105));
+	let board = board::deserialize_with(&str_board, &alphabet, &layout).unwrap();
+
+	let moves = solver::find_top_words("r0ez", &board, &tree, 100).unwrap();
+	let barre = moves.iter().find(|bw| bw.full_word == "bArre").expect("barre not found");
+	assert_eq!(barre.word, "___re");
+	assert_eq!(barre.coord, (7, 6));
+	assert!(!barre.vertical);
+	assert_eq!(barre.placed, vec![((7, 9), "r".to_string(), false), ((7, 10), "e".to_string(), false)]);
+	assert_eq!(barre.leave, "0z");
+
+	// A blank is placed as the letter it stands for
+	let with_blank = moves.iter().find(|bw| bw.placed.iter().any(|(_, _, blank)| *blank)).expect("no move with a blank");
+	assert!(!with_blank.leave.contains('0'));
+	for bw in &moves {
+		assert_eq!(bw.full_word, bw.breakdown.main_word);
+		assert_eq!(bw.placed.len() + bw.leave.chars().count(), 4);
+		for ((row, col), letter, blank) in &bw.placed {
+			let c = letter.chars().next().unwrap();
+			let expected = if *blank { to_blank(c) } else { c };
+			let position = if bw.vertical { row - bw.coord.0 } else { col - bw.coord.1 };
+			assert_eq!(bw.full_word.chars().nth(position), Some(expected));
+		}
+	}
+}
+
+use crate::notation::NotationError;
+
+#[test]
+fn move_notation() {
+	let alphabet = Alphabet::default();
+	let mv = solver::Move::from_notation("8H W(OR)D", &alphabet).unwrap();
+	assert!(!mv.vertical);
+	assert_eq!(mv.coord, (7, 7));
+	assert_eq!(mv.word, "w__d");
+	assert_eq!(mv.full_word, "word");
+	assert_eq!(mv.placed, vec![((7, 7), "w".to_string(), false), ((7, 10), "d".to_string(), false)]);
+	assert_eq!(mv.to_notation(&alphabet), Ok("8H W(OR)D".to_string()));
+	let mv = solver::Move::from_notation("8H W.(R)D", &alphabet).unwrap();
+	assert_eq!((mv.word.as_str(), mv.full_word.as_str()), ("w__d", "w_rd"));
+	assert_eq!(mv.to_notation(&alphabet), Ok("8H W.(R)D".to_string()));
+
+	// Blanks are lowercase, on the rack or on the board
+	let mv = solver::Move::from_notation("h8 (a)rBRE", &alphabet).unwrap();
+	assert!(mv.vertical);
+	assert_eq!(mv.coord, (7, 7));
+	assert_eq!(mv.word, "_Rbre");
+	assert_eq!(mv.full_word, "ARbre");
+	assert_eq!(mv.placed[0], ((8, 7), "r".to_string(), true));
+	assert_eq!(mv.to_notation(&alphabet), Ok("H8 (a)rBRE".to_string()));
+
+	// Found moves, and tiles of several letters
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let board = board::deserialize(&"_".repeat(225)).unwrap();
+	let first = solver::find_best_first_word("arbre", &board, &tree, None).unwrap().unwrap();
+	let notation = first.to_notation(&alphabet).unwrap();
+	assert_eq!(notation, format!("8{} ARBRE", (b'A' + first.coord.1 as u8) as char));
+	let read = solver::Move::from_notation(&notation, &alphabet).unwrap();
+	assert_eq!((read.vertical, read.coord, &read.word), (first.vertical, first.coord, &first.word));
+
+	let spanish = spanish_alphabet();
+	let mv = solver::Move::from_notation("1A [CH]O(RR)O", &spanish).unwrap();
+	assert_eq!(mv.full_word.chars().count(), 4);
+	assert_eq!(mv.to_notation(&spanish), Ok("1A [CH]O([RR])O".to_string()));
+
+	assert!(matches!(solver::Move::from_notation("8 WORD", &alphabet), Err(NotationError::WrongCoordinate(_))));
+	assert!(matches!(solver::Move::from_notation("0H WORD", &alphabet), Err(NotationError::WrongCoordinate(_))));
+	assert!(matches!(solver::Move::from_notation("8HH WORD", &alphabet), Err(NotationError::WrongCoordinate(_))));
+	assert!(matches!(solver::Move::from_notation("8H W(ORD", &alphabet), Err(NotationError::WrongWord(_))));
+	assert!(matches!(solver::Move::from_notation("8H W3RD", &alphabet), Err(NotationError::WrongWord(_))));
+	assert!(matches!(solver::Move::from_notation("8H", &alphabet), Err(NotationError::WrongWord(_))));
+	let unknown = solver::BestWord{word: "w__d".to_string(), ..Default::default()};
+	assert!(matches!(unknown.to_notation(&alphabet), Err(NotationError::WrongWord(_))));
+}
+
+use crate::gcg::{Game, GcgError, Action};
+
+#[test]
+fn gcg_game() {
+	let alphabet = TileSet::preset("english").unwrap().alphabet().clone();
+	let text = std::fs::read_to_string("src/test/game.gcg").unwrap();
+	let game = Game::parse(&text, &alphabet).unwrap();
+	assert_eq!(game.players, vec![
+		("alice".to_string(), "Alice Martin".to_string()), 
+		("bob".to_string(), "Bob Durand".to_string())]);
+	assert_eq!(game.headers[1], ("title".to_string(), "Training game".to_string()));
+	assert_eq!(game.notes, vec!["Played on the standard board, english tiles".to_string()]);
+	assert_eq!(game.turns.len(), 12);
+	assert_eq!(game.turns[0].notes, vec!["The only bingo lane is closed".to_string()]);
+	assert_eq!(game.turns[6].notes, vec!["QI is good, but not with the I under the R:\nit makes RI".to_string()]);
+	assert_eq!(game.turns[1].action, Action::Exchange("aio".to_string()));
+	assert_eq!(game.turns[2].rack, "deiosx0");
+	assert_eq!(game.turns[4].action, Action::Pass);
+	assert_eq!(game.turns[6].action, Action::PhonyWithdrawn);
+	assert_eq!(game.turns[8].action, Action::ChallengeBonus);
+	assert_eq!(game.turns[9].action, Action::TimePenalty);
+	assert_eq!(game.turns[10].action, Action::EndRack("eiqsuuv".to_string()));
+	assert_eq!(game.turns[11].action, Action::RackPenalty("eiqsuuv".to_string()));
+	match &game.turns[2].action {
+		Action::Play(mv) => assert_eq!((mv.vertical, mv.coord, mv.word.as_str()), (true, (7, 6), "_Ox")),
+		_ => panic!("Wrong action")
+	};
+
+	// The phony is taken back before the next move is played on its squares
+	let layout = std::sync::Arc::new(Layout::preset("scrabble").unwrap());
+	let mut board = board::Board::new_empty(&std::sync::Arc::new(alphabet.clone()), &layout);
+	game.replay(&mut board).unwrap();
+	let rows: Vec<String> = board.message().chars().collect::<Vec<char>>().chunks(15).map(|row| row.iter().collect()).collect();
+	assert_eq!(rows[7], "6__2__barter__6");
+	assert_eq!(rows[8], "__2___O_2_we2__");
+	assert_eq!(rows[9], "_3___3x__3___3_");
+
+	// Writing the game back gives the same game
+	assert_eq!(game.to_text().unwrap(), text);
+	assert_eq!(Game::parse(&game.to_text().unwrap(), &alphabet).unwrap(), game);
+
+	// Wrong scores are found, with their turn
+	let wrong_move = text.replace("9K WE +16 45", "9K WE +18 47");
+	let mut board = board::Board::new_empty(&std::sync::Arc::new(alphabet.clone()), &layout);
+	match Game::parse(&wrong_move, &alphabet).unwrap().replay(&mut board) {
+		Err(GcgError::ScoreMismatch(message)) => assert!(message.contains("turn 8"), "{}", message),
+		_ => panic!("Wrong error type")
+	};
+	let wrong_total = text.replace("(time) -10 0", "(time) -10 10");
+	let mut board = board::Board::new_empty(&std::sync::Arc::new(alphabet.clone()), &layout);
+	match Game::parse(&wrong_total, &alphabet).unwrap().replay(&mut board) {
+		Err(GcgError::ScoreMismatch(message)) => assert!(message.contains("turn 10"), "{}", message),
+		_ => panic!("Wrong error type")
+	};
+	// A withdrawn phony takes back exactly the score of the move
+	let wrong_withdrawal = text.replace("EIQSUUV -- -28 10", "EIQSUUV -- -20 18");
+	let mut board = board::Board::new_empty(&std::sync::Arc::new(alphabet.clone()), &layout);
+	match Game::parse(&wrong_withdrawal, &alphabet).unwrap().replay(&mut board) {
+		Err(GcgError::ScoreMismatch(message)) => assert!(message.contains("turn 7") && message.contains("scored 28"), "{}", message),
+		_ => panic!("Wrong error type")
+	};
+	let not_withdrawn = text.replace(">bob: EIQSUUV -- -28 10\n", "").replace("(time) -10 0", "(time) -10 28");
+	let mut board = board::Board::new_empty(&std::sync::Arc::new(alphabet.clone()), &layout);
+	assert!(matches!(Game::parse(&not_withdrawn, &alphabet).unwrap().replay(&mut board), Err(GcgError::IllegalMove(_))));
+
+	assert!(Game::parse(">carol: ABC - +0 0", &alphabet).is_ok());
+	assert!(matches!(Game::parse("#player1 alice\n>carol: ABC - +0 0", &alphabet), Err(GcgError::UnknownPlayer(_))));
+	assert!(matches!(Game::parse(">alice: ABC 8H +0 0", &alphabet), Err(GcgError::Syntax(_))));
+	assert!(matches!(Game::parse(">alice: ABC - +zero 0", &alphabet), Err(GcgError::Syntax(_))));
+	assert!(matches!(Game::from_file("src/test/not_a_file.gcg", &alphabet), Err(GcgError::UnreadableFile(_))));
+}
+
+use crate::cgp::{Position, CgpError};
+
+#[test]
+fn cgp_position() {
+	let alphabet = Alphabet::default();
+	let str_board = reference_board_message();
+	let cgp = "15/15/15/15/15/15/15/7A7/7R7/7BE6/7r7/7E7/15/15/15 SYSTE??/AEIRST 12/-3 2 lex ODS8; bag AEI;";
+
+	let position = Position::parse(cgp, &alphabet).unwrap();
+	assert_eq!(position.racks, vec!["syste00".to_string(), "aeirst".to_string()]);
+	assert_eq!(position.rack(), "syste00");
+	assert_eq!(position.scores, vec![12, -3]);
+	assert_eq!(position.zero_turns, 2);
+	assert_eq!(position.option("lex"), Some("ODS8"));
+	assert_eq!(position.option("bag"), Some("AEI"));
+	assert_eq!(position.option("tmr"), None);
+	assert_eq!(position.to_text(), cgp);
+
+	// Same board as the message, whose premiums are the ones of the layout
+	let layout = std::sync::Arc::new(Layout::preset("scrabble").unwrap());
+	let board = position.board(&layout).unwrap();
+	assert_eq!(board.message(), str_board);
+	let from_message = Position::from_message(&str_board, &alphabet).unwrap();
+	assert_eq!(from_message.message(), str_board);
+	assert!(from_message.to_text().starts_with("15/15/15/15/15/15/15/7A7/7R7/7BE6/7r7/7E7/15/15/15 / 0/0 0"));
+	// The CGP text has no premiums
+	assert_eq!(Position::parse(&from_message.to_text(), &alphabet).unwrap().message(), position.message());
+	assert_eq!(from_message.board(&layout).unwrap().message(), str_board);
+	// Premium squares of the message are kept over the ones of the layout
+	let custom_premiums = str_board.replacen("6__2", "7__4", 1);
+	let custom_position = Position::from_message(&custom_premiums, &alphabet).unwrap();
+	assert_eq!(custom_position.message(), custom_premiums);
+	assert_eq!(custom_position.board(&layout).unwrap().message(), custom_premiums);
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	assert_eq!(
+		solver::find_best_word::<WithoutTimer, _, _>("arbre", &board, &tree, None),
+		solver::find_best_word::<WithoutTimer, _, _>("arbre", &board::deserialize_with(&str_board, &std::sync::Arc::new(alphabet.clone()), &layout).unwrap(), &tree, None));
+
+	// Tiles of several letters are between brackets
+	let spanish = spanish_alphabet();
+	let position = Position::parse("[CH]O[rr]O1/5/5/5/5 [LL]?/ 0/0 0", &spanish).unwrap();
+	assert_eq!(position.message(), format!("[ch]o[RR]o_{}", "_".repeat(20)));
+	assert_eq!(position.to_text(), "[CH]O[rr]O1/5/5/5/5 [LL]?/ 0/0 0");
+
+	assert!(matches!(Position::parse("15/15 A/B 0/0", &alphabet), Err(CgpError::Syntax(_))));
+	assert!(matches!(Position::parse("2/3 A/B 0/0 0", &alphabet), Err(CgpError::WrongBoard(_))));
+	assert!(matches!(Position::parse("2/A A/B 0/0 0", &alphabet), Err(CgpError::WrongBoard(_))));
+	assert!(matches!(Position::parse("2/A3 A/B 0/0 0", &alphabet), Err(CgpError::WrongBoard(_))));
+	assert!(matches!(Position::parse("2/A% A/B 0/0 0", &alphabet), Err(CgpError::UnknownTile(_))));
+	assert!(matches!(Position::parse("2/2 A/B 0/x 0", &alphabet), Err(CgpError::Syntax(_))));
+	assert!(matches!(Position::from_message("abc", &alphabet), Err(CgpError::WrongBoard(_))));
+}
+
+#[test]
+fn board_round_trip() {
+	let mut str_board = "6__2___6___2__6".to_string();
+	str_board.push_str(&"_".repeat(90));
+	str_board.push_str("_______arBre___");
+	str_board.push_str("_______e_______");
+	str_board.push_str(&"_".repeat(90));
+	let board = board::deserialize(&str_board).unwrap();
+
+	// Empty squares are written with their premium
+	let message = board.serialize::<NotTransposed>();
+	assert_eq!(message, str_board);
+	assert_eq!(board.message(), message);
+	assert_eq!(board.serialize::<Transposed>().chars().nth(7*15 + 8), Some('e'));
+
+	assert_eq!(board::Board::deserialize::<NotTransposed>(&board.serialize::<NotTransposed>()).unwrap(), board);
+	assert_eq!(board::Board::deserialize::<Transposed>(&board.serialize::<Transposed>()).unwrap(), board);
+	assert_eq!(board::Board::deserialize::<NotTransposed>(&board.pretty::<NotTransposed>()).unwrap(), board);
+	assert_eq!(board::Board::deserialize::<Transposed>(&board.pretty::<Transposed>()).unwrap(), board);
+	assert_ne!(board::Board::deserialize::<NotTransposed>(&board.serialize::<Transposed>()).unwrap(), board);
+
+	let pretty = board.pretty::<NotTransposed>();
+	assert_eq!(pretty.lines().count(), 16);
+	assert_eq!(pretty.lines().next(), Some("   A B C D E F G H I J K L M N O"));
+	assert_eq!(pretty.lines().nth(1), Some(" 1 6 _ _ 2 _ _ _ 6 _ _ _ 2 _ _ 6"));
+	assert_eq!(pretty.lines().nth(8), Some(" 8 _ _ _ _ _ _ _ a r B r e _ _ _"));
+
+	// Grids without labels are read as well
+	let grid: String = pretty.lines().skip(1).map(|line| format!("{}\n", &line[3..])).collect();
+	assert_eq!(board::deserialize(&grid).unwrap(), board);
+	assert!(board::deserialize(&pretty.replace(" 8 _", " 8 _ _")).is_err());
+
+	// Indented rows are rows, only the letters of the columns make a header
+	let indented: String = pretty.lines().skip(1).map(|line| format!("    {}\n", &line[3..])).collect();
+	assert_eq!(board::deserialize(&indented).unwrap(), board);
+	let no_header: String = pretty.lines().skip(1).map(|line| format!("{}\n", line)).collect();
+	assert_eq!(board::deserialize(&no_header).unwrap(), board);
+	let missing_row: String = pretty.lines().filter(|line| !line.starts_with(" 9")).map(|line| format!("{}\n", line)).collect();
+	assert_eq!(board::deserialize(&missing_row).unwrap_err().problems(), vec![
+		&DeserializingError::WrongRowCount{expected: 15, found: 14},
+		&DeserializingError::WrongLength{expected: 225, found: 210}]);
+	assert_eq!(board::deserialize(&missing_row).unwrap_err().problems()[0].message(), "deserialize: 14 rows instead of 15");
+
+	let spanish = std::sync::Arc::new(spanish_alphabet());
+	let layout = std::sync::Arc::new(Layout::default());
+	let mut str_board = "[ch]o[RR]o".to_string();
+	str_board.push_str(&"_".repeat(221));
+	let board = board::deserialize_with(&str_board, &spanish, &layout).unwrap();
+	assert_eq!(board::deserialize_with(&board.pretty::<NotTransposed>(), &spanish, &layout).unwrap(), board);
+	assert_eq!(board::deserialize_with(&board.message(), &spanish, &layout).unwrap(), board);
+}
+
+use crate::board::BoardProblem;
+
+#[test]
+fn board_check() {
+	let tree = str_tree::build_dict_from_file("src/test/words.txt").expect("File not found");
+	let mut str_board = "_".repeat(105);
+	str_board.push_str("_______barre___");
+	str_board.push_str("_______a_______");
+	str_board.push_str("_______Rx______");
+	str_board.push_str(&"_".repeat(45));
+	str_board.push_str("_____________a_");
+	str_board.push_str(&"_".repeat(15));
+	let board = board::deserialize(&str_board).unwrap();
+	let tile_set = TileSet::new(&[
+		("a".to_string(), 1, 1),
+		("b".to_string(), 3, 1),
+		("e".to_string(), 1, 1),
+		("r".to_string(), 1, 2),
+		("x".to_string(), 8, 1)], 0).unwrap();
+
+	let problems = board.check(&tree, Some(&tile_set));
+	assert_eq!(problems, vec![
+		BoardProblem::NotAWord{word: "Rx".to_string(), coord: (9, 7), vertical: false},
+		BoardProblem::NotAWord{word: "a".to_string(), coord: (13, 13), vertical: false},
+		BoardProblem::Disconnected(vec![(13, 13)]),
+		BoardProblem::TooManyTiles{tile: "0".to_string(), count: 1, available: 0},
+		BoardProblem::TooManyTiles{tile: "a".to_string(), count: 3, available: 1}]);
+	assert_eq!(problems[0].message(), "check: Rx at 10H is not a word");
+	assert_eq!(problems[2].message(), "check: the tiles at 14N are not connected to the others");
+	assert_eq!(problems[4].message(), "check: 3 tiles a on the board, but 1 in the set");
+	assert_eq!(board.check(&tree, None).len(), 3);
+
+	// Without tile on the start square, the largest group is the one the others should be connected to
+	let mut str_board = "arbre_____bar__".to_string();
+	str_board.push_str("______b________");
+	str_board.push_str("______a________");
+	str_board.push_str("______b________");
+	str_board.push_str(&"_".repeat(165));
+	let board = board::deserialize(&str_board).unwrap();
+	let problems = board.check(&tree, None);
+	assert_eq!(problems, vec![
+		BoardProblem::StartEmpty,
+		BoardProblem::NotAWord{word: "bab".to_string(), coord: (1, 6), vertical: true},
+		BoardProblem::Disconnected(vec![(0, 10), (0, 11), (0, 12)]),
+		BoardProblem::Disconnected(vec![(1, 6), (2, 6), (3, 6)])]);
+	assert_eq!(problems[0].message(), "check: the start square is empty");
+	assert_eq!(problems[1].message(), "check: bab at G2 is not a word");
+
+	assert!(board::deserialize(&"_".repeat(225)).unwrap().check(&tree, Some(&tile_set)).is_empty());
+	let valid = board::deserialize(&format!("{}barre{}", "_".repeat(112), "_".repeat(108))).unwrap();
+	assert!(valid.check(&tree, None).is_empty());
+}
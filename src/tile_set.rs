@@ -0,0 +1,170 @@
+use crate::alphabet::{Alphabet, AlphabetError};
+
+use pyo3::prelude::{pyclass, pymethods};
+use pyo3::exceptions::PyValueError;
+
+/// Tile, value and count
+type PresetTile = (&'static str, usize, usize);
+
+// Official sets
+const ENGLISH: [PresetTile; 26] = [
+	("a", 1, 9), ("b", 3, 2), ("c", 3, 2), ("d", 2, 4), ("e", 1, 12), ("f", 4, 2), ("g", 2, 3),
+	("h", 4, 2), ("i", 1, 9), ("j", 8, 1), ("k", 5, 1), ("l", 1, 4), ("m", 3, 2), ("n", 1, 6),
+	("o", 1, 8), ("p", 3, 2), ("q", 10, 1), ("r", 1, 6), ("s", 1, 4), ("t", 1, 6), ("u", 1, 4),
+	("v", 4, 2), ("w", 4, 2), ("x", 8, 1), ("y", 4, 2), ("z", 10, 1)];
+
+const FRENCH: [PresetTile; 26] = [
+	("a", 1, 9), ("b", 3, 2), ("c", 3, 2), ("d", 2, 3), ("e", 1, 15), ("f", 4, 2), ("g", 2, 2),
+	("h", 4, 2), ("i", 1, 8), ("j", 8, 1), ("k", 10, 1), ("l", 1, 5), ("m", 2, 3), ("n", 1, 6),
+	("o", 1, 6), ("p", 3, 2), ("q", 8, 1), ("r", 1, 6), ("s", 1, 6), ("t", 1, 6), ("u", 1, 6),
+	("v", 4, 2), ("w", 10, 1), ("x", 10, 1), ("y", 10, 1), ("z", 10, 1)];
+
+const GERMAN: [PresetTile; 29] = [
+	("a", 1, 5), ("ä", 6, 1), ("b", 3, 2), ("c", 4, 2), ("d", 1, 4), ("e", 1, 15), ("f", 4, 2),
+	("g", 2, 3), ("h", 2, 4), ("i", 1, 6), ("j", 6, 1), ("k", 4, 2), ("l", 2, 3), ("m", 3, 4),
+	("n", 1, 9), ("o", 2, 3), ("ö", 8, 1), ("p", 4, 1), ("q", 10, 1), ("r", 1, 6), ("s", 1, 7),
+	("t", 1, 6), ("u", 1, 6), ("ü", 6, 1), ("v", 6, 1), ("w", 3, 1), ("x", 8, 1), ("y", 10, 1),
+	("z", 3, 1)];
+
+const SPANISH: [PresetTile; 28] = [
+	("a", 1, 12), ("b", 3, 2), ("c", 3, 4), ("ch", 5, 1), ("d", 2, 5), ("e", 1, 12), ("f", 4, 1),
+	("g", 2, 2), ("h", 4, 2), ("i", 1, 6), ("j", 8, 1), ("l", 1, 4), ("ll", 8, 1), ("m", 3, 2),
+	("n", 1, 5), ("ñ", 8, 1), ("o", 1, 9), ("p", 3, 2), ("q", 5, 1), ("r", 1, 5), ("rr", 8, 1),
+	("s", 1, 6), ("t", 1, 4), ("u", 1, 5), ("v", 4, 1), ("x", 8, 1), ("y", 4, 1), ("z", 10, 1)];
+
+const ITALIAN: [PresetTile; 21] = [
+	("a", 1, 14), ("b", 5, 3), ("c", 2, 6), ("d", 5, 3), ("e", 1, 11), ("f", 5, 3), ("g", 8, 2),
+	("h", 8, 2), ("i", 1, 12), ("l", 3, 5), ("m", 3, 5), ("n", 3, 5), ("o", 1, 15), ("p", 5, 3),
+	("q", 10, 1), ("r", 2, 6), ("s", 2, 6), ("t", 2, 6), ("u", 3, 5), ("v", 5, 3), ("z", 8, 2)];
+
+const DUTCH: [PresetTile; 26] = [
+	("a", 1, 6), ("b", 3, 2), ("c", 5, 2), ("d", 2, 5), ("e", 1, 18), ("f", 4, 2), ("g", 3, 3),
+	("h", 4, 2), ("i", 1, 4), ("j", 4, 2), ("k", 3, 3), ("l", 3, 3), ("m", 3, 3), ("n", 1, 10),
+	("o", 1, 6), ("p", 3, 2), ("q", 10, 1), ("r", 2, 5), ("s", 2, 5), ("t", 2, 5), ("u", 4, 3),
+	("v", 4, 2), ("w", 5, 2), ("x", 8, 1), ("y", 8, 1), ("z", 4, 2)];
+
+const POLISH: [PresetTile; 32] = [
+	("a", 1, 9), ("ą", 5, 1), ("b", 3, 2), ("c", 2, 3), ("ć", 6, 1), ("d", 2, 3), ("e", 1, 7),
+	("ę", 5, 1), ("f", 5, 1), ("g", 3, 2), ("h", 3, 2), ("i", 1, 8), ("j", 3, 2), ("k", 2, 3),
+	("l", 2, 3), ("ł", 3, 2), ("m", 2, 3), ("n", 1, 5), ("ń", 7, 1), ("o", 1, 6), ("ó", 5, 1),
+	("p", 2, 3), ("r", 1, 4), ("s", 1, 4), ("ś", 5, 1), ("t", 2, 3), ("u", 3, 2), ("w", 1, 4),
+	("y", 2, 4), ("z", 1, 5), ("ź", 9, 1), ("ż", 5, 1)];
+
+/// Name, tiles and number of blanks of the built-in sets
+const PRESETS: [(&str, &[PresetTile], usize); 7] = [
+	("english", &ENGLISH, 2),
+	("french", &FRENCH, 2),
+	("german", &GERMAN, 2),
+	("spanish", &SPANISH, 2),
+	("italian", &ITALIAN, 2),
+	("dutch", &DUTCH, 2),
+	("polish", &POLISH, 2)];
+
+/// Tiles of a game: the alphabet giving their values, how many of each are in the bag, and the number of blanks
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[pyclass]
+pub struct TileSet {
+	alphabet: Alphabet,
+	counts: Vec<usize>,
+	blanks: usize
+}
+
+impl TileSet {
+	/// Tiles as (text, value, count)
+	pub fn new(tiles: &[(String, usize, usize)], blanks: usize) -> Result<TileSet, AlphabetError> {
+		let values: Vec<(String, usize)> = tiles.iter().map(|(text, value, _)| (text.clone(), *value)).collect();
+		return Ok(TileSet{
+			alphabet: Alphabet::new(&values)?,
+			counts: tiles.iter().map(|(_, _, count)| *count).collect(),
+			blanks});
+	}
+
+	/// Built-in set of a language, by its english name, case insensitively
+	pub fn preset(name: &str) -> Option<TileSet> {
+		let (_, tiles, blanks) = PRESETS.iter().find(|(preset, _, _)| preset.eq_ignore_ascii_case(name))?;
+		let tiles: Vec<(String, usize, usize)> = tiles.iter()
+			.map(|(text, value, count)| (text.to_string(), *value, *count))
+			.collect();
+		return Some(TileSet::new(&tiles, *blanks).unwrap());
+	}
+
+	pub fn preset_names() -> Vec<&'static str> {
+		return PRESETS.iter().map(|(name, _, _)| *name).collect();
+	}
+
+	pub fn alphabet(&self) -> &Alphabet {
+		return &self.alphabet;
+	}
+
+	/// Number of tiles of a letter in the bag, blanks not included
+	pub fn count(&self, c: char) -> usize {
+		match self.alphabet.letters().iter().position(|letter| *letter == c) {
+			None => return 0,
+			Some(idx) => return self.counts[idx]
+		};
+	}
+
+	pub fn blanks(&self) -> usize {
+		return self.blanks;
+	}
+
+	/// Number of tiles in the bag, blanks included
+	pub fn total(&self) -> usize {
+		return self.counts.iter().sum::<usize>() + self.blanks;
+	}
+}
+
+#[pymethods]
+impl TileSet {
+	/// Tiles as (text, value, count) triples
+	#[new]
+	fn py_new(tiles: Vec<(String, usize, usize)>, blanks: usize) -> pyo3::PyResult<Self> {
+		return Ok(TileSet::new(&tiles, blanks)?);
+	}
+
+	#[staticmethod]
+	#[pyo3(name = "preset")]
+	fn py_preset(name: &str) -> pyo3::PyResult<Self> {
+		match TileSet::preset(name) {
+			None => return Err(pyo3::PyErr::new::<PyValueError, _>(format!("TileSet: unknown preset {}", name))),
+			Some(tile_set) => return Ok(tile_set)
+		};
+	}
+
+	#[staticmethod]
+	fn presets() -> Vec<&'static str> {
+		return TileSet::preset_names();
+	}
+
+	fn tiles(&self) -> Vec<(String, usize, usize)> {
+		return self.alphabet.tiles().into_iter().zip(self.counts.iter())
+			.map(|((text, value), count)| (text, value, *count))
+			.collect();
+	}
+
+	#[pyo3(name = "alphabet")]
+	fn py_alphabet(&self) -> Alphabet {
+		return self.alphabet.clone();
+	}
+
+	/// Number of tiles written `text` in the bag, none for an unknown tile
+	#[pyo3(name = "count")]
+	fn py_count(&self, text: &str) -> usize {
+		match self.alphabet.parse_tile(&text.to_lowercase()) {
+			None => return 0,
+			Some(c) => return self.count(c)
+		};
+	}
+
+	#[pyo3(name = "blanks")]
+	fn py_blanks(&self) -> usize {
+		return self.blanks();
+	}
+
+	#[pyo3(name = "total")]
+	fn py_total(&self) -> usize {
+		return self.total();
+	}
+}